@@ -1,40 +1,236 @@
-use std::{env, fs};
+use std::{env, path::PathBuf, slice};
 
 use tera::Context;
 
-use crate::internal::{self, App, AppMode};
+use super::{check_apps, confirm, WriteArgs};
+
+use crate::internal::{
+    self,
+    cargo::{CargoToml, CARGO_TOML},
+    framework::{self, Framework},
+    layout,
+    manifest::Manifest,
+    template::Template,
+    upgrade,
+    writer::{Policy, Writer},
+    AppMode, Component, Db, Options,
+};
 
 pub const TEMPLATE: &str = r#"
-🍺 App创建完成！请将以下配置加到Cargo.toml中：
-{% for app in apps %}
+🍺 App创建完成！已将以下配置加到Cargo.toml中：
+{% if workspace %}
+[workspace]
+members = [
+{%- for app in apps %}
+    "crates/{{ app.name }}",
+{%- endfor %}
+]
+{% else %}
+{%- for app in apps %}
 [[bin]]
 name = "{{ app.name }}"
 path = "{{ app.mainfile }}"
 {% endfor %}
+{%- endif %}
 "#;
 
-pub fn run(apps: Vec<String>, axum: bool) {
-    // 检查Cargo.toml是否存在
-    if fs::metadata("Cargo.toml").is_err() {
-        println!("Cargo.toml不存在，请确认！");
+pub fn run(
+    apps: Vec<String>,
+    framework: Option<&'static dyn Framework>,
+    template_dirs: Vec<PathBuf>,
+    db: Option<Db>,
+    without: Vec<Component>,
+    write: WriteArgs,
+    yes: bool,
+) {
+    if !check_apps(&apps) {
         return;
     }
 
     // 获取当前目录
     let dir = env::current_dir().unwrap().canonicalize().unwrap();
 
-    let mut bins = Vec::<App>::new();
-    for name in apps {
-        bins.push(App {
-            name: name.clone(),
-            mainfile: format!("src/app/{}/main.rs", name),
-        });
+    let w = write.writer(&dir);
+
+    // 读取Cargo.toml
+    let mut cargo = match CargoToml::load(&w, &dir) {
+        Ok(v) => v,
+        Err(e) => {
+            println!("👿 {}", e);
+            return;
+        }
+    };
+    let workspace = cargo.is_workspace();
+    let registered = cargo.apps();
+    // 单应用项目（转换为多应用后再创建）
+    let mut single = registered
+        .iter()
+        .find(|v| v.1 == layout::SINGLE_MAINFILE)
+        .map(|v| v.0.clone());
+    if let Some(v) = apps.iter().find(|v| registered.iter().any(|b| &&b.0 == v)) {
+        if w.policy() == Policy::Abort || single.as_ref() == Some(v) {
+            println!("👿 应用({})已存在（{}），请确认！", v, CARGO_TOML);
+            return;
+        }
+    }
+
+    // 读取 yiirs.toml（旧项目以命令行参数为准）
+    let mut manifest = match Manifest::load(&dir) {
+        Ok(v) => v,
+        Err(e) => {
+            println!("👿 {}", e);
+            return;
+        }
+    };
+    let (framework, opts, template_dirs) = match &manifest {
+        Some(m) => {
+            // workspace 的单应用同样位于 crates/<name>，以 yiirs.toml 为准
+            if m.mode == AppMode::Single && workspace {
+                single = registered.first().map(|v| v.0.clone());
+            }
+            if m.mode == AppMode::Single && single.is_none() {
+                println!("👿 {}中未找到单应用的 [[bin]]，请确认！", CARGO_TOML);
+                return;
+            }
+            if let Some(v) = apps.iter().find(|v| m.apps.contains(v)) {
+                if w.policy() == Policy::Abort {
+                    println!("👿 应用({})已存在，请确认！", v);
+                    return;
+                }
+            }
+            let framework = match m.framework(framework) {
+                Ok(v) => v,
+                Err(e) => {
+                    println!("👿 {}", e);
+                    return;
+                }
+            };
+            // 命令行指定的数据库及组件须与项目一致
+            if (db.is_some() || !without.is_empty())
+                && Options::new(db.unwrap_or(m.db), &without, m.workspace) != m.options()
+            {
+                println!(
+                    "👿 数据库及组件与项目不一致（{}），请确认！",
+                    internal::manifest::MANIFEST
+                );
+                return;
+            }
+            (framework, m.options(), m.template_dirs(template_dirs))
+        }
+        None if workspace => {
+            println!(
+                "👿 workspace 项目缺少 {}，请确认！",
+                internal::manifest::MANIFEST
+            );
+            return;
+        }
+        None => (
+            framework.unwrap_or(framework::REGISTRY[0]),
+            Options::new(db.unwrap_or(Db::Mysql), &without, false),
+            template_dirs,
+        ),
+    };
+
+    if let Some(name) = &single {
+        let prompt = format!(
+            "单应用项目，是否将应用({})转换为多应用项目（{}）？[y/N] ",
+            name,
+            opts.app_dir(name, AppMode::Multi)
+        );
+        if !yes && !w.dry_run() && !confirm(&prompt) {
+            println!("👿 单应用项目不支持创建应用，请确认！");
+            return;
+        }
     }
 
-    if axum {
-        internal::build_axum_app(&dir, &bins, AppMode::Multi);
-    } else {
-        internal::build_salvo_app(&dir, &bins, AppMode::Multi);
+    // 加载模板
+    let template = match Template::load(framework, &template_dirs) {
+        Ok(v) => v,
+        Err(e) => {
+            println!("👿 {}", e);
+            return;
+        }
+    };
+
+    let bins = apps
+        .iter()
+        .map(|v| opts.app(v, AppMode::Multi))
+        .collect::<Vec<_>>();
+
+    // 先完成所有冲突检查（[[bin]]、应用文件），再转换及生成文件
+    if let Some(name) = single.as_ref().filter(|_| !workspace) {
+        let mainfile = opts.app(name, AppMode::Multi).mainfile;
+        if let Err(e) = cargo.set_bin_path(name, &mainfile) {
+            println!("👿 {}", e);
+            return;
+        }
+    }
+    if let Err(e) = cargo.add_apps(&bins) {
+        println!("👿 {}", e);
+        return;
+    }
+    if !internal::check_app(&w, &dir, &bins, AppMode::Multi, &template, opts) {
+        return;
+    }
+    // 转换后的基线（多应用的渲染结果），模板有误时不做任何修改
+    let rebased = match (&single, &manifest) {
+        (Some(name), Some(m)) => {
+            let project = if m.name.is_empty() { name } else { &m.name };
+            match internal::render(project, slice::from_ref(name), &template, opts) {
+                Ok(v) => Some(v),
+                Err(e) => {
+                    println!("👿 {}", e);
+                    return;
+                }
+            }
+        }
+        _ => None,
+    };
+
+    // 单应用转换为多应用
+    if let Some(name) = &single {
+        println!("🍺 转换为多应用: {}", name);
+        if let Err(e) = layout::to_multi(&w, &dir, name, opts) {
+            println!("👿 {}", e);
+            return;
+        }
+
+        if let Some(m) = manifest.as_mut() {
+            if m.name.is_empty() {
+                m.name = name.clone();
+            }
+            m.mode = AppMode::Multi;
+            m.apps = vec![name.clone()];
+
+            // 基线改为多应用的渲染结果
+            if let Some(files) = &rebased {
+                upgrade::rebase(&w, &dir, files);
+            }
+        }
+    }
+
+    if !internal::build_app(&w, &dir, &bins, AppMode::Multi, &template, opts) {
+        return;
+    }
+
+    // 添加 [[bin]] 或 workspace 成员
+    cargo.save(&w);
+    println!("{}", CARGO_TOML);
+
+    // 记录应用
+    if let Some(m) = manifest.as_mut() {
+        for v in apps {
+            if !m.apps.contains(&v) {
+                m.apps.push(v);
+            }
+        }
+        m.save(&w, &dir);
+        println!("{}", internal::manifest::MANIFEST);
+    }
+
+    if w.dry_run() {
+        w.finish();
+        return;
     }
 
     let mut tera = tera::Tera::default();
@@ -42,6 +238,206 @@ pub fn run(apps: Vec<String>, axum: bool) {
 
     let mut ctx = Context::new();
     ctx.insert("apps", &bins);
+    ctx.insert("workspace", &workspace);
 
     println!("{}", tera.render("app", &ctx).unwrap());
 }
+
+pub fn remove(name: String, yes: bool, dry_run: bool) {
+    let Some(mut p) = Project::open(dry_run) else {
+        return;
+    };
+    if !p.has_app(&name) {
+        return;
+    }
+    if p.cargo.apps().len() <= 1 {
+        println!("👿 至少保留一个应用，请确认！");
+        return;
+    }
+
+    let prompt = format!(
+        "是否删除应用({})的代码（src/app/{}）、配置文件及Docker文件？[y/N] ",
+        name, name
+    );
+    if !yes && !dry_run && !confirm(&prompt) {
+        return;
+    }
+
+    if let Err(e) = p.cargo.remove_app(&name) {
+        println!("👿 {}", e);
+        return;
+    }
+
+    println!("🍺 删除应用: {}", name);
+    layout::remove_app(&p.w, &p.dir, &name, p.opts);
+    p.save(|apps| apps.retain(|v| v != &name));
+
+    if dry_run {
+        p.w.finish();
+        return;
+    }
+    println!("🍺 应用删除完成！");
+}
+
+pub fn rename(from: String, to: String, dry_run: bool) {
+    if !check_apps([&to]) {
+        return;
+    }
+    let Some(mut p) = Project::open(dry_run) else {
+        return;
+    };
+    if !p.has_app(&from) {
+        return;
+    }
+    if p.cargo.apps().iter().any(|v| v.0 == to) {
+        println!("👿 应用({})已存在（{}），请确认！", to, CARGO_TOML);
+        return;
+    }
+
+    let mainfile = p.opts.app(&to, AppMode::Multi).mainfile;
+    if let Err(e) = p.cargo.rename_app(&from, &to, &mainfile) {
+        println!("👿 {}", e);
+        return;
+    }
+
+    println!("🍺 重命名应用: {} -> {}", from, to);
+    if let Err(e) = layout::rename_app(&p.w, &p.dir, &from, &to, p.opts) {
+        println!("👿 {}", e);
+        return;
+    }
+    p.save(|apps| {
+        for v in apps.iter_mut().filter(|v| *v == &from) {
+            v.clone_from(&to);
+        }
+    });
+
+    if dry_run {
+        p.w.finish();
+        return;
+    }
+    println!("🍺 应用重命名完成！");
+}
+
+// 多应用项目（yiirs app remove、yiirs app rename）
+struct Project {
+    dir: PathBuf,
+    w: Writer,
+    cargo: CargoToml,
+    manifest: Option<Manifest>,
+    // 用于更新基线（旧项目没有 yiirs.toml 时为 None）
+    template: Option<Template>,
+    opts: Options,
+}
+
+impl Project {
+    fn open(dry_run: bool) -> Option<Self> {
+        // 获取当前目录
+        let dir = env::current_dir().unwrap().canonicalize().unwrap();
+
+        let w = Writer::new(&dir, dry_run, Policy::Abort);
+
+        let cargo = match CargoToml::load(&w, &dir) {
+            Ok(v) => v,
+            Err(e) => {
+                println!("👿 {}", e);
+                return None;
+            }
+        };
+        if cargo.apps().iter().any(|v| v.1 == layout::SINGLE_MAINFILE) {
+            println!("👿 单应用项目不支持该操作，请确认！");
+            return None;
+        }
+        let workspace = cargo.is_workspace();
+
+        let mut manifest = match Manifest::load(&dir) {
+            Ok(v) => v,
+            Err(e) => {
+                println!("👿 {}", e);
+                return None;
+            }
+        };
+        // 项目名称（兼容未记录名称的 yiirs.toml）
+        if let Some(m) = manifest.as_mut().filter(|m| m.name.is_empty()) {
+            m.name = dir.file_name().unwrap().to_string_lossy().to_string();
+        }
+        let template = match &manifest {
+            Some(m) if m.mode == AppMode::Single => {
+                println!("👿 单应用项目不支持该操作，请确认！");
+                return None;
+            }
+            Some(m) => {
+                let framework = match m.framework(None) {
+                    Ok(v) => v,
+                    Err(e) => {
+                        println!("👿 {}", e);
+                        return None;
+                    }
+                };
+                // 提前渲染，模板有误时不做任何修改
+                let template = Template::load(framework, &m.template_dirs(Vec::new()))
+                    .and_then(|v| internal::render(&m.name, &m.apps, &v, m.options()).map(|_| v));
+                match template {
+                    Ok(v) => Some(v),
+                    Err(e) => {
+                        println!("👿 {}", e);
+                        return None;
+                    }
+                }
+            }
+            None if workspace => {
+                println!(
+                    "👿 workspace 项目缺少 {}，请确认！",
+                    internal::manifest::MANIFEST
+                );
+                return None;
+            }
+            None => None,
+        };
+        // 仅用于确定项目结构（旧项目为非 workspace）
+        let opts = match &manifest {
+            Some(m) => m.options(),
+            None => Options::new(Db::Mysql, &[], false),
+        };
+
+        Some(Project {
+            dir,
+            w,
+            cargo,
+            manifest,
+            template,
+            opts,
+        })
+    }
+
+    fn has_app(&self, name: &str) -> bool {
+        let mainfile = self.opts.app(name, AppMode::Multi).mainfile;
+        if !self
+            .cargo
+            .apps()
+            .iter()
+            .any(|v| v.0 == name && v.1 == mainfile)
+        {
+            println!("👿 应用({})不存在（{}），请确认！", name, CARGO_TOML);
+            return false;
+        }
+        true
+    }
+
+    // 保存 Cargo.toml 及 yiirs.toml，并更新基线
+    fn save(&mut self, update: impl FnOnce(&mut Vec<String>)) {
+        self.cargo.save(&self.w);
+        println!("{}", CARGO_TOML);
+
+        let (Some(m), Some(template)) = (self.manifest.as_mut(), &self.template) else {
+            return;
+        };
+        update(&mut m.apps);
+        m.save(&self.w, &self.dir);
+        println!("{}", internal::manifest::MANIFEST);
+
+        match internal::render(&m.name, &m.apps, template, self.opts) {
+            Ok(files) => upgrade::rebase(&self.w, &self.dir, &files),
+            Err(e) => println!("👿 {}（未更新基线）", e),
+        }
+    }
+}
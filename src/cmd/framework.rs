@@ -0,0 +1,8 @@
+use crate::internal::framework::REGISTRY;
+
+pub fn run() {
+    for (i, v) in REGISTRY.iter().enumerate() {
+        let default = if i == 0 { "（默认）" } else { "" };
+        println!("{:<8}{}{}", v.name(), v.homepage(), default);
+    }
+}
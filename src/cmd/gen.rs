@@ -0,0 +1,250 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use tera::Tera;
+
+use super::WriteArgs;
+use crate::internal::{
+    framework::{self, Framework},
+    gen::{self, Method},
+    manifest::Manifest,
+    schema::{self, SqlDialect},
+    template::Template,
+    writer::{Policy, Writer},
+    AppMode, Db,
+};
+
+pub fn run_api(
+    app: Option<String>,
+    name: String,
+    method: Method,
+    framework: Option<&'static dyn Framework>,
+    template_dirs: Vec<PathBuf>,
+    write: WriteArgs,
+) {
+    // 检查Cargo.toml是否存在
+    if fs::metadata("Cargo.toml").is_err() {
+        println!("Cargo.toml不存在，请确认！");
+        return;
+    }
+
+    // 获取当前目录
+    let dir = env::current_dir().unwrap().canonicalize().unwrap();
+
+    // 项目清单及模板
+    let (manifest, tera) = match load(&dir, framework, template_dirs) {
+        Some(v) => v,
+        None => return,
+    };
+
+    // 应用目录
+    let app_dir = match app_dir(&dir, &app, &manifest) {
+        Some(v) => v,
+        None => return,
+    };
+
+    let w = write.writer(&dir);
+    gen::build_api(&w, &app_dir, &name, method, &tera);
+    w.finish();
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn run_entity(
+    schema_file: PathBuf,
+    dialect: SqlDialect,
+    tables: Vec<String>,
+    crud: bool,
+    app: Option<String>,
+    framework: Option<&'static dyn Framework>,
+    template_dirs: Vec<PathBuf>,
+    write: WriteArgs,
+) {
+    // 检查Cargo.toml是否存在
+    if fs::metadata("Cargo.toml").is_err() {
+        println!("Cargo.toml不存在，请确认！");
+        return;
+    }
+
+    // 获取当前目录
+    let dir = env::current_dir().unwrap().canonicalize().unwrap();
+
+    // 项目清单及模板
+    let (manifest, tera) = match load(&dir, framework, template_dirs) {
+        Some(v) => v,
+        None => return,
+    };
+
+    // 项目未使用数据库（yiirs new --db=none）
+    if !use_db(&dir, &manifest) {
+        println!("👿 项目未使用数据库，请确认！");
+        return;
+    }
+
+    let sql = match fs::read_to_string(&schema_file) {
+        Ok(v) => v,
+        Err(e) => {
+            println!("👿 读取文件({:?})失败：{}", schema_file, e);
+            return;
+        }
+    };
+    let mut list = match schema::parse(&sql, dialect) {
+        Ok(v) => v,
+        Err(e) => {
+            println!("👿 {}", e);
+            return;
+        }
+    };
+    // 指定数据表
+    if !tables.is_empty() {
+        list.retain(|v| tables.contains(&v.name));
+    }
+    if list.is_empty() {
+        println!("👿 未找到数据表（CREATE TABLE），请确认！");
+        return;
+    }
+
+    // CRUD需要先确认应用目录
+    let app_dir = if crud {
+        match app_dir(&dir, &app, &manifest) {
+            Some(v) => Some(v),
+            None => return,
+        }
+    } else {
+        None
+    };
+
+    let w = write.writer(&dir);
+
+    // 先检查CRUD文件，避免只生成了Entity
+    if let Some(app_dir) = &app_dir {
+        let files = list
+            .iter()
+            .flat_map(|v| gen::slice_files(app_dir, &v.module))
+            .collect::<Vec<_>>();
+        if !w.check(&files) {
+            return;
+        }
+    }
+
+    // entity
+    if !gen::build_entity(&w, &internal_dir(&dir, &manifest), &list, gen::entity()) {
+        return;
+    }
+
+    // crud
+    if let Some(app_dir) = app_dir {
+        for table in &list {
+            gen::build_crud(&w, &app_dir, table, &tera);
+        }
+    }
+
+    w.finish();
+}
+
+pub fn run_migration(name: String, dry_run: bool) {
+    // 检查Cargo.toml是否存在
+    if fs::metadata("Cargo.toml").is_err() {
+        println!("Cargo.toml不存在，请确认！");
+        return;
+    }
+
+    // 获取当前目录
+    let dir = env::current_dir().unwrap().canonicalize().unwrap();
+
+    let manifest = match Manifest::load(&dir) {
+        Ok(v) => v,
+        Err(e) => {
+            println!("👿 {}", e);
+            return;
+        }
+    };
+    if !use_db(&dir, &manifest) {
+        println!("👿 项目未使用数据库，请确认！");
+        return;
+    }
+
+    let w = Writer::new(&dir, dry_run, Policy::Abort);
+    gen::build_migration(&w, &internal_dir(&dir, &manifest), &name, gen::migration());
+    w.finish();
+}
+
+// 读取 yiirs.toml 确定框架及模板目录（旧项目以命令行参数为准）
+fn load(
+    dir: &Path,
+    framework: Option<&'static dyn Framework>,
+    template_dirs: Vec<PathBuf>,
+) -> Option<(Option<Manifest>, Tera)> {
+    let manifest = match Manifest::load(dir) {
+        Ok(v) => v,
+        Err(e) => {
+            println!("👿 {}", e);
+            return None;
+        }
+    };
+    let (framework, template_dirs) = match &manifest {
+        Some(m) => match m.framework(framework) {
+            Ok(v) => (v, m.template_dirs(template_dirs)),
+            Err(e) => {
+                println!("👿 {}", e);
+                return None;
+            }
+        },
+        None => (framework.unwrap_or(framework::REGISTRY[0]), template_dirs),
+    };
+    match Template::load(framework, &template_dirs).and_then(|v| v.gen()) {
+        Ok(v) => Some((manifest, v)),
+        Err(e) => {
+            println!("👿 {}", e);
+            None
+        }
+    }
+}
+
+fn use_db(dir: &Path, manifest: &Option<Manifest>) -> bool {
+    match manifest {
+        Some(m) => m.db != Db::None,
+        None => fs::metadata(dir.join("src").join("internal").join("core").join("db.rs")).is_ok(),
+    }
+}
+
+// 内部库源码目录：src/internal，workspace 为 crates/internal/src
+fn internal_dir(dir: &Path, manifest: &Option<Manifest>) -> PathBuf {
+    match manifest {
+        Some(m) => dir.join(m.options().internal_dir()),
+        None => dir.join("src").join("internal"),
+    }
+}
+
+fn app_dir(dir: &Path, app: &Option<String>, manifest: &Option<Manifest>) -> Option<PathBuf> {
+    // 按项目模式校验 --app
+    if let Some(m) = manifest {
+        match (m.mode, app) {
+            (AppMode::Single, Some(_)) => {
+                println!("👿 单应用项目无需指定 --app，请确认！");
+                return None;
+            }
+            (AppMode::Multi, None) => {
+                println!("👿 多应用项目请指定 --app（{}）", m.apps.join("、"));
+                return None;
+            }
+            (AppMode::Multi, Some(v)) if !m.apps.contains(v) => {
+                println!("👿 应用({})不存在（{}），请确认！", v, m.apps.join("、"));
+                return None;
+            }
+            _ => {}
+        }
+    }
+    let app_dir = match (manifest, app) {
+        (Some(m), Some(v)) => dir.join(m.options().app_dir(v, AppMode::Multi)),
+        (Some(m), None) => dir.join(m.options().app_dir(&m.name, AppMode::Single)),
+        (None, Some(v)) => dir.join("src").join("app").join(v),
+        (None, None) => dir.join("src").join("app"),
+    };
+    if fs::metadata(app_dir.join("main.rs")).is_err() {
+        println!("👿 应用目录({:?})不存在，请确认！", app_dir);
+        return None;
+    }
+    Some(app_dir)
+}
@@ -1,7 +1,24 @@
 pub mod app;
+pub mod framework;
+pub mod gen;
 pub mod project;
+pub mod secrets;
+pub mod upgrade;
 
-use clap::{Parser, Subcommand};
+use clap::{Args, Parser, Subcommand};
+
+use std::{
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use crate::internal::{
+    framework::names,
+    gen::{is_ident, Method},
+    schema::SqlDialect,
+    writer::{Policy, Writer},
+    Component, Db,
+};
 
 #[derive(Parser)]
 #[command(author, version, about, long_about = None)]
@@ -15,15 +32,233 @@ pub enum Command {
     New {
         #[arg(short, long)]
         name: String,
-        #[arg(short, long)]
+        /// 同 --framework=axum
+        #[arg(long)]
         axum: bool,
+        /// Web框架（默认：salvo）
+        #[arg(long, value_parser = names(), conflicts_with = "axum")]
+        framework: Option<String>,
+        /// 自定义模板目录（覆盖或扩展内置模板，可多次指定）
+        #[arg(long, value_name = "DIR")]
+        template_dir: Vec<PathBuf>,
         #[arg(short, long)]
         app: Vec<String>,
+        /// 数据库（none 表示不使用数据库）
+        #[arg(long, value_enum, default_value_t = Db::Mysql)]
+        db: Db,
+        /// 不使用的组件（lock 依赖 redis）
+        #[arg(long, value_enum)]
+        without: Vec<Component>,
+        /// 使用 Cargo workspace：crates/internal 及每个应用一个 crate
+        #[arg(long)]
+        workspace: bool,
+        #[command(flatten)]
+        write: WriteArgs,
     },
+    /// 创建应用（多应用），或删除、重命名应用
+    #[command(args_conflicts_with_subcommands = true)]
     App {
+        #[command(subcommand)]
+        command: Option<AppCommand>,
         #[arg(short, long)]
         name: Vec<String>,
+        /// 同 --framework=axum
         #[arg(short, long)]
         axum: bool,
+        /// Web框架（默认读取 yiirs.toml，旧项目默认：salvo）
+        #[arg(long, value_parser = names(), conflicts_with = "axum")]
+        framework: Option<String>,
+        /// 自定义模板目录（覆盖或扩展内置模板，可多次指定）
+        #[arg(long, value_name = "DIR")]
+        template_dir: Vec<PathBuf>,
+        /// 数据库（默认读取 yiirs.toml，旧项目默认：mysql）
+        #[arg(long, value_enum)]
+        db: Option<Db>,
+        /// 不使用的组件（默认读取 yiirs.toml）
+        #[arg(long, value_enum)]
+        without: Vec<Component>,
+        #[command(flatten)]
+        write: WriteArgs,
+        /// 单应用项目转换为多应用时不再确认
+        #[arg(short, long)]
+        yes: bool,
+    },
+    /// 列出支持的Web框架
+    Frameworks,
+    /// 使用当前版本的模板升级项目（与项目中的修改三方合并）
+    Upgrade {
+        /// 自定义模板目录（在 yiirs.toml 记录的目录之后加载）
+        #[arg(long, value_name = "DIR")]
+        template_dir: Vec<PathBuf>,
+        /// 冲突时保留原文件，并生成 .rej 文件（默认写入冲突标记）
+        #[arg(long)]
+        reject: bool,
+        /// 仅预览：输出文件树及与已有文件的差异，不写入任何文件
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// 代码生成
+    Gen {
+        #[command(subcommand)]
+        command: GenCommand,
+    },
+    /// 配置项加解密（enc:...），与生成项目的 secrets 命令一致
+    Secrets {
+        #[command(subcommand)]
+        command: SecretsCommand,
+    },
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum AppCommand {
+    /// 删除应用（代码、[[bin]]、配置文件及Docker文件）
+    Remove {
+        name: String,
+        /// 不再确认
+        #[arg(short, long)]
+        yes: bool,
+        /// 仅预览：输出文件树及与已有文件的差异，不写入任何文件
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// 重命名应用
+    Rename {
+        from: String,
+        to: String,
+        /// 仅预览：输出文件树及与已有文件的差异，不写入任何文件
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum GenCommand {
+    /// 生成API（api、service、路由注册）
+    Api {
+        /// 应用名称（多应用项目）
+        #[arg(long)]
+        app: Option<String>,
+        #[arg(short, long)]
+        name: String,
+        #[arg(short, long, value_enum, default_value_t = Method::Post)]
+        method: Method,
+        /// 同 --framework=axum
+        #[arg(long)]
+        axum: bool,
+        /// Web框架（默认读取 yiirs.toml，旧项目默认：salvo）
+        #[arg(long, value_parser = names(), conflicts_with = "axum")]
+        framework: Option<String>,
+        /// 自定义模板目录（覆盖或扩展内置模板，可多次指定）
+        #[arg(long, value_name = "DIR")]
+        template_dir: Vec<PathBuf>,
+        #[command(flatten)]
+        write: WriteArgs,
     },
+    /// 根据SQL（DDL或数据库导出文件）生成sea-orm实体，可选生成CRUD
+    Entity {
+        /// SQL文件
+        #[arg(short, long, value_name = "FILE")]
+        schema: PathBuf,
+        #[arg(short, long, value_enum, default_value_t = SqlDialect::Mysql)]
+        dialect: SqlDialect,
+        /// 指定数据表（默认全部）
+        #[arg(short, long)]
+        table: Vec<String>,
+        /// 同时生成CRUD（service、api、路由注册）
+        #[arg(long)]
+        crud: bool,
+        /// 应用名称（多应用项目）
+        #[arg(long)]
+        app: Option<String>,
+        /// 同 --framework=axum
+        #[arg(long)]
+        axum: bool,
+        /// Web框架（默认读取 yiirs.toml，旧项目默认：salvo）
+        #[arg(long, value_parser = names(), conflicts_with = "axum")]
+        framework: Option<String>,
+        /// 自定义模板目录（覆盖或扩展内置模板，可多次指定）
+        #[arg(long, value_name = "DIR")]
+        template_dir: Vec<PathBuf>,
+        #[command(flatten)]
+        write: WriteArgs,
+    },
+    /// 生成数据库迁移文件（internal/migration）
+    Migration {
+        /// 迁移名称，如：create_user
+        name: String,
+        /// 仅预览：输出文件树及与已有文件的差异，不写入任何文件
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum SecretsCommand {
+    /// 加密配置项的值，输出 enc:...
+    Encrypt {
+        value: String,
+        /// 主密钥文件（默认：环境变量 APP_MASTER_KEY，其次为 APP_MASTER_KEY_FILE 或 master.key）
+        #[arg(long, value_name = "FILE")]
+        key_file: Option<PathBuf>,
+    },
+    /// 解密 enc:... 的值
+    Decrypt {
+        value: String,
+        /// 主密钥文件（默认：环境变量 APP_MASTER_KEY，其次为 APP_MASTER_KEY_FILE 或 master.key）
+        #[arg(long, value_name = "FILE")]
+        key_file: Option<PathBuf>,
+    },
+    /// 生成随机的主密钥
+    Keygen {
+        /// 写入密钥文件，如：master.key（默认输出到终端）
+        #[arg(short, long, value_name = "FILE")]
+        output: Option<PathBuf>,
+    },
+}
+
+// 文件写入选项
+#[derive(Args, Debug, Clone)]
+pub struct WriteArgs {
+    /// 仅预览：输出文件树及与已有文件的差异，不写入任何文件
+    #[arg(long)]
+    pub dry_run: bool,
+    /// 覆盖已存在的文件
+    #[arg(long, conflicts_with = "skip_existing")]
+    pub force: bool,
+    /// 跳过已存在的文件
+    #[arg(long)]
+    pub skip_existing: bool,
+}
+
+impl WriteArgs {
+    pub fn writer(&self, dir: &Path) -> Writer {
+        Writer::new(
+            dir,
+            self.dry_run,
+            Policy::new(self.force, self.skip_existing),
+        )
+    }
+}
+
+// 终端确认，输入 y 或 yes 返回 true
+pub fn confirm(prompt: &str) -> bool {
+    print!("{}", prompt);
+    io::stdout().flush().unwrap();
+
+    let mut input = String::new();
+    if io::stdin().read_line(&mut input).is_err() {
+        return false;
+    }
+    matches!(input.trim().to_lowercase().as_str(), "y" | "yes")
+}
+
+// 应用名称同时用作目录、文件及 [[bin]] 名称，须为合法的标识符
+pub fn check_apps<'a>(names: impl IntoIterator<Item = &'a String>) -> bool {
+    for v in names {
+        if !is_ident(v) {
+            println!("👿 应用名称({})不合法，请使用小写字母、数字和下划线", v);
+            return false;
+        }
+    }
+    true
 }
@@ -1,27 +1,62 @@
-use std::{env, fs};
+use std::{env, path::PathBuf};
 
-use crate::internal::{self, is_empty_dir};
+use super::{check_apps, WriteArgs};
+use crate::internal::{
+    self, framework::Framework, is_empty_dir, manifest::Manifest, template::Template,
+    writer::Policy, AppMode, Options,
+};
+
+pub fn run(
+    name: String,
+    framework: &'static dyn Framework,
+    template_dirs: Vec<PathBuf>,
+    apps: Vec<String>,
+    opts: Options,
+    write: WriteArgs,
+) {
+    if !check_apps(&apps) {
+        return;
+    }
+
+    // 加载模板
+    let template = match Template::load(framework, &template_dirs) {
+        Ok(v) => v,
+        Err(e) => {
+            println!("👿 {}", e);
+            return;
+        }
+    };
 
-pub fn run(name: String, axum: bool, apps: Vec<String>) {
     // 获取当前目录
     let dir = env::current_dir().unwrap().canonicalize().unwrap();
     // 项目跟目录
     let root = dir.join(&name);
 
-    // 判断目录是否为空
-    if !is_empty_dir(&root) {
+    let w = write.writer(&dir);
+
+    // 判断目录是否为空（--force、--skip-existing 时按文件处理）
+    if w.policy() == Policy::Abort && !is_empty_dir(&root) {
         println!("👿 目录({:?})不为空，请确认！", root);
         return;
     }
-    // 创建项目目录
-    fs::create_dir_all(root.clone()).unwrap();
 
     // 创建项目
-    if axum {
-        internal::build_axum_project(&root, &name, &apps);
-    } else {
-        internal::build_salvo_project(&root, &name, &apps);
+    if !internal::build_project(&w, &root, &name, &apps, &template, opts) {
+        return;
     }
 
+    // 记录项目生成方式
+    let mode = if apps.is_empty() {
+        AppMode::Single
+    } else {
+        AppMode::Multi
+    };
+    Manifest::new(&name, framework, mode, &apps, opts, &template_dirs).save(&w, &root);
+    println!("{}", internal::manifest::MANIFEST);
+
+    if w.dry_run() {
+        w.finish();
+        return;
+    }
     println!("🍺 项目创建完成！请阅读README")
 }
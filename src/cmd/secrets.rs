@@ -0,0 +1,61 @@
+use std::path::PathBuf;
+
+use crate::internal::secrets;
+
+use super::SecretsCommand;
+
+pub fn run(command: SecretsCommand) {
+    match command {
+        SecretsCommand::Encrypt { value, key_file } => {
+            let key = match secrets::master_key(key_file.as_deref()) {
+                Ok(v) => v,
+                Err(e) => {
+                    println!("👿 {}", e);
+                    return;
+                }
+            };
+            match secrets::encrypt(&key, &value) {
+                Ok(v) => println!("{}", v),
+                Err(e) => println!("👿 加密失败：{}", e),
+            }
+        }
+        SecretsCommand::Decrypt { value, key_file } => {
+            let key = match secrets::master_key(key_file.as_deref()) {
+                Ok(v) => v,
+                Err(e) => {
+                    println!("👿 {}", e);
+                    return;
+                }
+            };
+            match secrets::decrypt(&key, &value) {
+                Ok(v) => println!("{}", v),
+                Err(e) => println!("👿 {}", e),
+            }
+        }
+        SecretsCommand::Keygen { output } => keygen(output),
+    }
+}
+
+// 生成主密钥，指定 --output 时写入密钥文件（已存在则不覆盖）
+fn keygen(output: Option<PathBuf>) {
+    let key = secrets::keygen();
+    let path = match output {
+        Some(v) => v,
+        None => {
+            println!("{}", key);
+            return;
+        }
+    };
+    if path.exists() {
+        println!("👿 文件({})已存在，请确认！", path.display());
+        return;
+    }
+    if let Err(e) = std::fs::write(&path, format!("{}\n", key)) {
+        println!("👿 {}写入失败：{}", path.display(), e);
+        return;
+    }
+    println!(
+        "🍺 主密钥已写入{}，请妥善保管（勿提交到代码仓库）",
+        path.display()
+    );
+}
@@ -0,0 +1,104 @@
+use std::{env, fs, path::PathBuf};
+
+use crate::internal::{
+    self,
+    manifest::{Manifest, MANIFEST},
+    template::Template,
+    upgrade,
+    writer::{Policy, Writer},
+};
+
+pub fn run(template_dirs: Vec<PathBuf>, reject: bool, dry_run: bool) {
+    // 检查Cargo.toml是否存在
+    if fs::metadata("Cargo.toml").is_err() {
+        println!("Cargo.toml不存在，请确认！");
+        return;
+    }
+
+    // 获取当前目录
+    let dir = env::current_dir().unwrap().canonicalize().unwrap();
+
+    let mut manifest = match Manifest::load(&dir) {
+        Ok(Some(v)) => v,
+        Ok(None) => {
+            println!("👿 {}不存在，无法确定项目的生成方式，请确认！", MANIFEST);
+            return;
+        }
+        Err(e) => {
+            println!("👿 {}", e);
+            return;
+        }
+    };
+    let framework = match manifest.framework(None) {
+        Ok(v) => v,
+        Err(e) => {
+            println!("👿 {}", e);
+            return;
+        }
+    };
+    let template = match Template::load(framework, &manifest.template_dirs(template_dirs)) {
+        Ok(v) => v,
+        Err(e) => {
+            println!("👿 {}", e);
+            return;
+        }
+    };
+
+    // 项目名称（兼容未记录名称的 yiirs.toml）
+    if manifest.name.is_empty() {
+        manifest.name = dir.file_name().unwrap().to_string_lossy().to_string();
+    }
+    let opts = manifest.options();
+
+    // 使用当前版本的模板重新渲染
+    let files = match internal::render(&manifest.name, &manifest.apps, &template, opts) {
+        Ok(v) => v,
+        Err(e) => {
+            println!("👿 {}", e);
+            return;
+        }
+    };
+
+    println!(
+        "🍺 升级项目: {} -> {}",
+        manifest.version,
+        env!("CARGO_PKG_VERSION")
+    );
+
+    let w = Writer::new(&dir, dry_run, Policy::Force);
+    let summary = upgrade::upgrade(&w, &dir, &files, reject);
+
+    manifest.version = String::from(env!("CARGO_PKG_VERSION"));
+    manifest.save(&w, &dir);
+
+    print_list("新增", &summary.created);
+    print_list("更新", &summary.updated);
+    print_list("合并", &summary.merged);
+    print_list("冲突（请处理冲突标记）", &summary.conflicted);
+    print_list("冲突（请参考 .rej 文件手动合并）", &summary.rejected);
+    print_list("已删除，跳过", &summary.skipped);
+    print_list("模板已移除，请手动处理", &summary.removed);
+
+    if w.dry_run() {
+        w.finish();
+        return;
+    }
+    if summary.conflicted.is_empty() && summary.rejected.is_empty() {
+        println!("🍺 项目升级完成！");
+    } else {
+        println!(
+            "👿 项目升级完成，{}个文件存在冲突，请手动处理！",
+            summary.conflicted.len() + summary.rejected.len()
+        );
+    }
+}
+
+fn print_list(title: &str, files: &[String]) {
+    if files.is_empty() {
+        return;
+    }
+    println!("{}：", title);
+    for v in files {
+        println!("  {}", v);
+    }
+}
@@ -0,0 +1,104 @@
+use super::framework::{Framework, Templates};
+
+pub struct Actix;
+
+impl Framework for Actix {
+    fn name(&self) -> &'static str {
+        "actix"
+    }
+
+    fn title(&self) -> &'static str {
+        "actix-web"
+    }
+
+    fn homepage(&self) -> &'static str {
+        "https://github.com/actix/actix-web"
+    }
+
+    fn dependencies(&self) -> &'static str {
+        include_str!("../../template/actix/dependencies.tera")
+    }
+
+    fn internal(&self) -> Templates {
+        vec![
+            // middleware
+            (
+                "middleware/catch_panic.rs",
+                include_str!("../../template/actix/internal/middleware/catch_panic.tera"),
+            ),
+            (
+                "middleware/log.rs",
+                include_str!("../../template/actix/internal/middleware/log.tera"),
+            ),
+            (
+                "middleware/metrics.rs",
+                include_str!("../../template/actix/internal/middleware/metrics.tera"),
+            ),
+            (
+                "middleware/trace.rs",
+                include_str!("../../template/actix/internal/middleware/trace.tera"),
+            ),
+            // result
+            (
+                "result/code.rs",
+                include_str!("../../template/actix/internal/result/code.tera"),
+            ),
+            (
+                "result/rejection.rs",
+                include_str!("../../template/actix/internal/result/rejection.tera"),
+            ),
+            (
+                "result/reply.rs",
+                include_str!("../../template/actix/internal/result/reply.tera"),
+            ),
+        ]
+    }
+
+    fn app(&self) -> Templates {
+        vec![
+            // api
+            (
+                "api/greeter.rs",
+                include_str!("../../template/actix/app/api/greeter.tera"),
+            ),
+            (
+                "api/health.rs",
+                include_str!("../../template/actix/app/api/health.tera"),
+            ),
+            (
+                "api/metrics.rs",
+                include_str!("../../template/actix/app/api/metrics.tera"),
+            ),
+            // cmd
+            (
+                "cmd/serve.rs",
+                include_str!("../../template/actix/app/cmd/serve.tera"),
+            ),
+            // middleware
+            (
+                "middleware/auth.rs",
+                include_str!("../../template/actix/app/middleware/auth.tera"),
+            ),
+            // router
+            (
+                "router/route.rs",
+                include_str!("../../template/actix/app/router/route.tera"),
+            ),
+        ]
+    }
+
+    fn gen(&self) -> Templates {
+        vec![
+            ("api", include_str!("../../template/actix/gen/api.tera")),
+            ("route", include_str!("../../template/actix/gen/route.tera")),
+            (
+                "crud_api",
+                include_str!("../../template/actix/gen/crud_api.tera"),
+            ),
+            (
+                "crud_route",
+                include_str!("../../template/actix/gen/crud_route.tera"),
+            ),
+        ]
+    }
+}
@@ -0,0 +1,411 @@
+use std::path::{Path, PathBuf};
+
+use toml_edit::{value, Array, ArrayOfTables, DocumentMut, Item, Table};
+
+use super::{writer::Writer, App};
+
+pub const CARGO_TOML: &str = "Cargo.toml";
+
+// 编辑项目的 Cargo.toml（保留原有的格式及注释）
+pub struct CargoToml {
+    path: PathBuf,
+    doc: DocumentMut,
+}
+
+impl CargoToml {
+    // dir：项目根目录或 workspace 成员目录
+    pub fn load(w: &Writer, dir: &Path) -> Result<Self, String> {
+        let path = dir.join(CARGO_TOML);
+        let content = match w.read(&path) {
+            Some(v) => v,
+            None => return Err(format!("{}不存在，请确认！", CARGO_TOML)),
+        };
+        let doc = content
+            .parse::<DocumentMut>()
+            .map_err(|e| format!("解析文件({:?})失败：{}", path, e))?;
+        Ok(CargoToml { path, doc })
+    }
+
+    // workspace 项目（根目录为虚拟清单）
+    pub fn is_workspace(&self) -> bool {
+        self.doc.contains_key("workspace") && !self.doc.contains_key("package")
+    }
+
+    // 应用列表：(name, mainfile)，workspace 为 crates/ 下的成员（不含 internal）
+    pub fn apps(&self) -> Vec<(String, String)> {
+        if !self.is_workspace() {
+            return self.bins();
+        }
+        self.members()
+            .iter()
+            .filter_map(|v| v.strip_prefix("crates/"))
+            .filter(|v| *v != "internal")
+            .map(|v| (v.to_string(), format!("crates/{}/src/main.rs", v)))
+            .collect()
+    }
+
+    // 添加应用：[[bin]] 或 workspace.members
+    pub fn add_apps(&mut self, apps: &[App]) -> Result<(), String> {
+        if !self.is_workspace() {
+            return self.add_bins(apps);
+        }
+        let members = self.members();
+        for app in apps {
+            let member = format!("crates/{}", app.name);
+            if !members.contains(&member) {
+                self.members_mut()?.push(member);
+            }
+        }
+        Ok(())
+    }
+
+    // 删除应用：[[bin]] 或 workspace.members
+    pub fn remove_app(&mut self, name: &str) -> Result<(), String> {
+        if !self.is_workspace() {
+            return self.remove_bin(name);
+        }
+        let member = format!("crates/{}", name);
+        let members = self.members_mut()?;
+        let idx = members
+            .iter()
+            .position(|v| v.as_str() == Some(member.as_str()));
+        match idx {
+            Some(i) => {
+                members.remove(i);
+                Ok(())
+            }
+            None => Err(format!(
+                "{}中不存在 workspace 成员 \"{}\"，请确认！",
+                CARGO_TOML, member
+            )),
+        }
+    }
+
+    // 重命名应用：[[bin]] 或 workspace.members
+    pub fn rename_app(&mut self, from: &str, to: &str, mainfile: &str) -> Result<(), String> {
+        if !self.is_workspace() {
+            return self.rename_bin(from, to, mainfile);
+        }
+        let member = format!("crates/{}", from);
+        let members = self.members_mut()?;
+        let idx = members
+            .iter()
+            .position(|v| v.as_str() == Some(member.as_str()));
+        match idx {
+            Some(i) => {
+                members.replace(i, format!("crates/{}", to));
+                Ok(())
+            }
+            None => Err(format!(
+                "{}中不存在 workspace 成员 \"{}\"，请确认！",
+                CARGO_TOML, member
+            )),
+        }
+    }
+
+    // 重命名 package（workspace 成员的 Cargo.toml）
+    pub fn rename_package(&mut self, name: &str) {
+        self.doc["package"]["name"] = value(name);
+        if let Some(bins) = self
+            .doc
+            .get_mut("bin")
+            .and_then(|v| v.as_array_of_tables_mut())
+        {
+            for t in bins.iter_mut() {
+                t["name"] = value(name);
+            }
+        }
+    }
+
+    fn members(&self) -> Vec<String> {
+        self.doc
+            .get("workspace")
+            .and_then(|v| v.get("members"))
+            .and_then(|v| v.as_array())
+            .map(|v| {
+                v.iter()
+                    .filter_map(|v| v.as_str().map(String::from))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    fn members_mut(&mut self) -> Result<&mut Array, String> {
+        self.doc
+            .get_mut("workspace")
+            .and_then(|v| v.get_mut("members"))
+            .and_then(|v| v.as_array_mut())
+            .ok_or(format!(
+                "{}中不存在 workspace.members，请确认！",
+                CARGO_TOML
+            ))
+    }
+
+    // [[bin]] 列表：(name, path)
+    fn bins(&self) -> Vec<(String, String)> {
+        let bins = match self.doc.get("bin").and_then(|v| v.as_array_of_tables()) {
+            Some(v) => v,
+            None => return Vec::new(),
+        };
+        bins.iter()
+            .map(|t| {
+                let name = t.get("name").and_then(|v| v.as_str()).unwrap_or_default();
+                let path = t.get("path").and_then(|v| v.as_str()).unwrap_or_default();
+                (name.to_string(), path.to_string())
+            })
+            .collect()
+    }
+
+    // 添加 [[bin]]，已存在相同的配置则跳过，名称或路径冲突则报错
+    fn add_bins(&mut self, apps: &[App]) -> Result<(), String> {
+        let bins = self.bins();
+        for app in apps {
+            for (name, path) in &bins {
+                if name == &app.name && path == &app.mainfile {
+                    continue;
+                }
+                if name == &app.name || path == &app.mainfile {
+                    return Err(format!(
+                        "{}中已存在 [[bin]] name = \"{}\", path = \"{}\"，请确认！",
+                        CARGO_TOML, name, path
+                    ));
+                }
+            }
+        }
+
+        let item = self
+            .doc
+            .entry("bin")
+            .or_insert(Item::ArrayOfTables(ArrayOfTables::new()));
+        let bin = match item.as_array_of_tables_mut() {
+            Some(v) => v,
+            None => {
+                return Err(format!(
+                    "{}中的 bin 不是 [[bin]] 格式，请确认！",
+                    CARGO_TOML
+                ))
+            }
+        };
+        for app in apps {
+            if bins.iter().any(|v| v.0 == app.name) {
+                continue;
+            }
+            let mut t = Table::new();
+            t.insert("name", value(&app.name));
+            t.insert("path", value(&app.mainfile));
+            bin.push(t);
+        }
+        Ok(())
+    }
+
+    // 修改 [[bin]] 的路径
+    pub fn set_bin_path(&mut self, name: &str, path: &str) -> Result<(), String> {
+        match self.find_bin(name) {
+            Some(t) => {
+                t["path"] = value(path);
+                Ok(())
+            }
+            None => Err(format!(
+                "{}中不存在 [[bin]] name = \"{}\"，请确认！",
+                CARGO_TOML, name
+            )),
+        }
+    }
+
+    // 重命名 [[bin]]（名称及路径）
+    fn rename_bin(&mut self, from: &str, to: &str, path: &str) -> Result<(), String> {
+        self.set_bin_path(from, path)?;
+        if let Some(t) = self.find_bin(from) {
+            t["name"] = value(to);
+        }
+        Ok(())
+    }
+
+    // 删除 [[bin]]
+    fn remove_bin(&mut self, name: &str) -> Result<(), String> {
+        let bins = self
+            .doc
+            .get_mut("bin")
+            .and_then(|v| v.as_array_of_tables_mut());
+        if let Some(bins) = bins {
+            let idx = bins
+                .iter()
+                .position(|t| t.get("name").and_then(|v| v.as_str()) == Some(name));
+            if let Some(i) = idx {
+                bins.remove(i);
+                return Ok(());
+            }
+        }
+        Err(format!(
+            "{}中不存在 [[bin]] name = \"{}\"，请确认！",
+            CARGO_TOML, name
+        ))
+    }
+
+    fn find_bin(&mut self, name: &str) -> Option<&mut Table> {
+        self.doc
+            .get_mut("bin")
+            .and_then(|v| v.as_array_of_tables_mut())
+            .and_then(|v| {
+                v.iter_mut()
+                    .find(|t| t.get("name").and_then(|v| v.as_str()) == Some(name))
+            })
+    }
+
+    pub fn save(&self, w: &Writer) {
+        w.write(&self.path, &self.doc.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PACKAGE: &str = r#"[package]
+name = "demo"
+version = "0.1.0"
+
+# 应用
+[[bin]]
+name = "foo"
+path = "src/app/foo/main.rs"
+
+[dependencies]
+anyhow = "1.0"
+"#;
+
+    fn parse(content: &str) -> CargoToml {
+        CargoToml {
+            path: PathBuf::from(CARGO_TOML),
+            doc: content.parse().unwrap(),
+        }
+    }
+
+    fn app(name: &str) -> App {
+        App {
+            name: name.to_string(),
+            mainfile: format!("src/app/{}/main.rs", name),
+        }
+    }
+
+    #[test]
+    fn add_bins() {
+        let mut cargo = parse(PACKAGE);
+        cargo.add_apps(&[app("foo"), app("bar")]).unwrap();
+        assert_eq!(
+            cargo.apps(),
+            vec![
+                ("foo".to_string(), "src/app/foo/main.rs".to_string()),
+                ("bar".to_string(), "src/app/bar/main.rs".to_string()),
+            ]
+        );
+        // 保留原有的注释及其他配置
+        let content = cargo.doc.to_string();
+        assert!(content.contains("# 应用\n[[bin]]"));
+        assert!(content.contains("anyhow = \"1.0\""));
+    }
+
+    #[test]
+    fn add_bins_conflict() {
+        let mut cargo = parse(PACKAGE);
+        let name = App {
+            name: "foo".to_string(),
+            mainfile: "src/main.rs".to_string(),
+        };
+        let path = App {
+            name: "baz".to_string(),
+            mainfile: "src/app/foo/main.rs".to_string(),
+        };
+        assert!(cargo.add_apps(&[app("bar"), name]).is_err());
+        assert!(cargo.add_apps(&[path]).is_err());
+        // 冲突时不做任何修改
+        assert_eq!(cargo.doc.to_string(), PACKAGE);
+    }
+
+    #[test]
+    fn add_bins_without_bin() {
+        let mut cargo = parse("[package]\nname = \"demo\"\n");
+        cargo.add_apps(&[app("foo")]).unwrap();
+        assert_eq!(cargo.apps().len(), 1);
+
+        let mut cargo = parse("bin = 1\n\n[package]\nname = \"demo\"\n");
+        assert!(cargo.add_apps(&[app("foo")]).is_err());
+    }
+
+    #[test]
+    fn set_bin_path() {
+        let mut cargo = parse(PACKAGE);
+        cargo.set_bin_path("foo", "src/app/bar/main.rs").unwrap();
+        assert_eq!(
+            cargo.apps(),
+            vec![("foo".to_string(), "src/app/bar/main.rs".to_string())]
+        );
+        assert!(cargo.set_bin_path("bar", "src/main.rs").is_err());
+    }
+
+    const WORKSPACE: &str = r#"[workspace]
+resolver = "2"
+members = [
+    "crates/internal",
+    "crates/foo",
+]
+"#;
+
+    #[test]
+    fn rename_bin() {
+        let mut cargo = parse(PACKAGE);
+        cargo
+            .rename_app("foo", "bar", "src/app/bar/main.rs")
+            .unwrap();
+        assert_eq!(
+            cargo.apps(),
+            vec![("bar".to_string(), "src/app/bar/main.rs".to_string())]
+        );
+        assert!(cargo
+            .rename_app("foo", "baz", "src/app/baz/main.rs")
+            .is_err());
+    }
+
+    #[test]
+    fn rename_member() {
+        let mut cargo = parse(WORKSPACE);
+        cargo.rename_app("foo", "bar", "").unwrap();
+        assert_eq!(
+            cargo.apps(),
+            vec![("bar".to_string(), "crates/bar/src/main.rs".to_string())]
+        );
+        assert!(cargo.rename_app("foo", "baz", "").is_err());
+    }
+
+    #[test]
+    fn workspace_members() {
+        let mut cargo = parse(WORKSPACE);
+        assert!(cargo.is_workspace());
+        assert!(!parse(PACKAGE).is_workspace());
+
+        // 已存在的成员不重复添加
+        cargo.add_apps(&[app("foo"), app("bar")]).unwrap();
+        assert_eq!(
+            cargo.apps(),
+            vec![
+                ("foo".to_string(), "crates/foo/src/main.rs".to_string()),
+                ("bar".to_string(), "crates/bar/src/main.rs".to_string()),
+            ]
+        );
+
+        cargo.remove_app("foo").unwrap();
+        assert!(cargo.remove_app("foo").is_err());
+        assert_eq!(cargo.members(), vec!["crates/internal", "crates/bar"]);
+    }
+
+    #[test]
+    fn rename_package() {
+        let mut cargo =
+            parse("[package]\nname = \"foo\"\n\n[[bin]]\nname = \"foo\"\npath = \"src/main.rs\"\n");
+        cargo.rename_package("bar");
+        assert_eq!(
+            cargo.doc.to_string(),
+            "[package]\nname = \"bar\"\n\n[[bin]]\nname = \"bar\"\npath = \"src/main.rs\"\n"
+        );
+    }
+}
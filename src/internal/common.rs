@@ -0,0 +1,251 @@
+use std::vec;
+
+use tera::Tera;
+
+use super::framework::Templates;
+
+// 各框架通用的模板，框架模板与之重名时以框架模板为准
+
+pub fn global() -> Tera {
+    let mut tera = Tera::default();
+    // 使用 include_str! 宏将模板文件嵌入到二进制文件中
+    tera.add_raw_templates(vec![
+        (
+            "Cargo.toml",
+            include_str!("../../template/common/Cargo.tera"),
+        ),
+        (
+            ".dockerignore",
+            include_str!("../../template/common/dockerignore.tera"),
+        ),
+        (
+            ".gitignore",
+            include_str!("../../template/common/gitignore.tera"),
+        ),
+        (
+            "README.md",
+            include_str!("../../template/common/README.tera"),
+        ),
+    ])
+    .unwrap();
+    tera
+}
+
+pub fn docker() -> Tera {
+    let mut tera = Tera::default();
+    // 使用 include_str! 宏将模板文件嵌入到二进制文件中
+    tera.add_raw_templates(vec![(
+        "Dockerfile",
+        include_str!("../../template/common/Dockerfile.tera"),
+    )])
+    .unwrap();
+    tera
+}
+
+pub fn other() -> Tera {
+    let mut tera = Tera::default();
+    // 使用 include_str! 宏将模板文件嵌入到二进制文件中
+    tera.add_raw_templates(vec![
+        (
+            "dockerun.sh",
+            include_str!("../../template/common/dockerun.tera"),
+        ),
+        (
+            "config.toml",
+            include_str!("../../template/common/config.tera"),
+        ),
+    ])
+    .unwrap();
+    tera
+}
+
+// workspace 成员的 Cargo.toml（internal：crates/internal，app：crates/<应用>）
+pub fn crates() -> Tera {
+    let mut tera = Tera::default();
+    // 使用 include_str! 宏将模板文件嵌入到二进制文件中
+    tera.add_raw_templates(vec![
+        (
+            "internal",
+            include_str!("../../template/common/crates/internal.tera"),
+        ),
+        ("app", include_str!("../../template/common/crates/app.tera")),
+    ])
+    .unwrap();
+    tera
+}
+
+pub fn internal() -> Templates {
+    vec![
+        // lib.rs
+        (
+            "lib.rs",
+            include_str!("../../template/common/internal/lib.tera"),
+        ),
+        // core
+        (
+            "core/mod.rs",
+            include_str!("../../template/common/internal/core/mod.tera"),
+        ),
+        (
+            "core/cache.rs",
+            include_str!("../../template/common/internal/core/cache.tera"),
+        ),
+        (
+            "core/config.rs",
+            include_str!("../../template/common/internal/core/config.tera"),
+        ),
+        (
+            "core/db.rs",
+            include_str!("../../template/common/internal/core/db.tera"),
+        ),
+        (
+            "core/health.rs",
+            include_str!("../../template/common/internal/core/health.tera"),
+        ),
+        (
+            "core/logger.rs",
+            include_str!("../../template/common/internal/core/logger.tera"),
+        ),
+        (
+            "core/manager.rs",
+            include_str!("../../template/common/internal/core/manager.tera"),
+        ),
+        (
+            "core/metrics.rs",
+            include_str!("../../template/common/internal/core/metrics.tera"),
+        ),
+        (
+            "core/otel.rs",
+            include_str!("../../template/common/internal/core/otel.tera"),
+        ),
+        (
+            "core/secrets.rs",
+            include_str!("../../template/common/internal/core/secrets.tera"),
+        ),
+        (
+            "core/shutdown.rs",
+            include_str!("../../template/common/internal/core/shutdown.tera"),
+        ),
+        // crypto
+        (
+            "crypto/mod.rs",
+            include_str!("../../template/common/internal/crypto/mod.tera"),
+        ),
+        (
+            "crypto/aes.rs",
+            include_str!("../../template/common/internal/crypto/aes.tera"),
+        ),
+        (
+            "crypto/hash.rs",
+            include_str!("../../template/common/internal/crypto/hash.tera"),
+        ),
+        // middleware
+        (
+            "middleware/mod.rs",
+            include_str!("../../template/common/internal/middleware/mod.tera"),
+        ),
+        // migration
+        (
+            "migration/mod.rs",
+            include_str!("../../template/migration/mod.tera"),
+        ),
+        // result
+        (
+            "result/mod.rs",
+            include_str!("../../template/common/internal/result/mod.tera"),
+        ),
+        // util
+        (
+            "util/mod.rs",
+            include_str!("../../template/common/internal/util/mod.tera"),
+        ),
+        (
+            "util/helper.rs",
+            include_str!("../../template/common/internal/util/helper.tera"),
+        ),
+        (
+            "util/identity.rs",
+            include_str!("../../template/common/internal/util/identity.tera"),
+        ),
+        (
+            "util/jwt.rs",
+            include_str!("../../template/common/internal/util/jwt.tera"),
+        ),
+        (
+            "util/mutex.rs",
+            include_str!("../../template/common/internal/util/mutex.tera"),
+        ),
+        (
+            "util/xtime.rs",
+            include_str!("../../template/common/internal/util/xtime.tera"),
+        ),
+    ]
+}
+
+pub fn app() -> Templates {
+    vec![
+        // main.rs
+        (
+            "main.rs",
+            include_str!("../../template/common/app/main.tera"),
+        ),
+        // api
+        (
+            "api/mod.rs",
+            include_str!("../../template/common/app/api/mod.tera"),
+        ),
+        // cmd
+        (
+            "cmd/mod.rs",
+            include_str!("../../template/common/app/cmd/mod.tera"),
+        ),
+        (
+            "cmd/config.rs",
+            include_str!("../../template/common/app/cmd/config.tera"),
+        ),
+        (
+            "cmd/hello.rs",
+            include_str!("../../template/common/app/cmd/hello.tera"),
+        ),
+        (
+            "cmd/migrate.rs",
+            include_str!("../../template/common/app/cmd/migrate.tera"),
+        ),
+        (
+            "cmd/secrets.rs",
+            include_str!("../../template/common/app/cmd/secrets.tera"),
+        ),
+        // middleware
+        (
+            "middleware/mod.rs",
+            include_str!("../../template/common/app/middleware/mod.tera"),
+        ),
+        // router
+        (
+            "router/mod.rs",
+            include_str!("../../template/common/app/router/mod.tera"),
+        ),
+        // service
+        (
+            "service/mod.rs",
+            include_str!("../../template/common/app/service/mod.tera"),
+        ),
+        (
+            "service/greeter.rs",
+            include_str!("../../template/common/app/service/greeter.tera"),
+        ),
+    ]
+}
+
+pub fn gen() -> Templates {
+    vec![
+        (
+            "service",
+            include_str!("../../template/common/gen/service.tera"),
+        ),
+        (
+            "crud_service",
+            include_str!("../../template/common/gen/crud_service.tera"),
+        ),
+    ]
+}
@@ -0,0 +1,72 @@
+use tera::Tera;
+
+use super::{actix::Actix, axum::Axum, common, poem::Poem, salvo::Salvo};
+
+// 模板列表：(文件名, 模板内容)
+pub type Templates = Vec<(&'static str, &'static str)>;
+
+// Web框架（模板集）
+// 新增框架只需实现该 trait 并加入 REGISTRY，通用模板见 common
+pub trait Framework: Sync {
+    // 名称（--framework 的取值）
+    fn name(&self) -> &'static str;
+    // 框架全称（README）
+    fn title(&self) -> &'static str;
+    // 框架主页（README）
+    fn homepage(&self) -> &'static str;
+    // Cargo.toml 中框架相关的依赖
+    fn dependencies(&self) -> &'static str;
+    // internal 模板（与 common 重名时覆盖）
+    fn internal(&self) -> Templates;
+    // app 模板（与 common 重名时覆盖）
+    fn app(&self) -> Templates;
+    // gen 模板（与 common 重名时覆盖）
+    fn gen(&self) -> Templates;
+}
+
+// 已注册的框架，第一个为默认框架
+pub static REGISTRY: &[&dyn Framework] = &[&Salvo, &Axum, &Actix, &Poem];
+
+pub fn names() -> Vec<&'static str> {
+    REGISTRY.iter().map(|v| v.name()).collect()
+}
+
+pub fn find(name: &str) -> Option<&'static dyn Framework> {
+    REGISTRY.iter().find(|v| v.name() == name).copied()
+}
+
+// `--axum` 等同于 `--framework=axum`
+pub fn choose(framework: Option<String>, axum: bool) -> Option<&'static dyn Framework> {
+    let name = match framework {
+        Some(v) => v,
+        None if axum => String::from("axum"),
+        None => return None,
+    };
+    // clap 已校验取值
+    find(&name)
+}
+
+// 未指定时使用默认框架
+pub fn select(framework: Option<String>, axum: bool) -> &'static dyn Framework {
+    choose(framework, axum).unwrap_or(REGISTRY[0])
+}
+
+pub fn internal(framework: &dyn Framework) -> Tera {
+    merge(common::internal(), framework.internal())
+}
+
+pub fn app(framework: &dyn Framework) -> Tera {
+    merge(common::app(), framework.app())
+}
+
+pub fn gen(framework: &dyn Framework) -> Tera {
+    merge(common::gen(), framework.gen())
+}
+
+fn merge(common: Templates, own: Templates) -> Tera {
+    let mut tera = Tera::default();
+    tera.add_raw_templates(common).unwrap();
+    // 同名模板后添加者覆盖
+    tera.add_raw_templates(own).unwrap();
+    tera
+}
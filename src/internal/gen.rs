@@ -0,0 +1,524 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use tera::{Context, Tera};
+
+use super::{
+    schema::{camel_case, Table},
+    template,
+    writer::{Policy, Writer},
+};
+
+// 路由插入标记，`yiirs gen` 只会在该标记所在行之前插入代码
+pub const ROUTE_MARKER: &str = "@yiirs:routes";
+
+// 迁移插入标记（migration/mod.rs）
+pub const MIGRATION_MOD_MARKER: &str = "@yiirs:migration-mods";
+pub const MIGRATION_MARKER: &str = "@yiirs:migrations";
+
+// sea_orm::prelude 中的类型
+const PRELUDE_TYPES: &[&str] = &[
+    "Date",
+    "DateTime",
+    "DateTimeWithTimeZone",
+    "Decimal",
+    "Json",
+    "Time",
+    "Uuid",
+];
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug)]
+pub enum Method {
+    Get,
+    Post,
+    Put,
+    Delete,
+}
+
+impl Method {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Method::Get => "get",
+            Method::Post => "post",
+            Method::Put => "put",
+            Method::Delete => "delete",
+        }
+    }
+
+    // 默认的处理函数名称
+    pub fn handler(&self) -> &'static str {
+        match self {
+            Method::Get => "info",
+            Method::Post => "create",
+            Method::Put => "update",
+            Method::Delete => "delete",
+        }
+    }
+}
+
+pub fn is_ident(name: &str) -> bool {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(c) if c.is_ascii_lowercase() => {
+            chars.all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+        }
+        _ => false,
+    }
+}
+
+pub fn build_api(w: &Writer, app_dir: &Path, name: &str, method: Method, tera: &Tera) {
+    if !is_ident(name) {
+        println!("👿 名称({})不合法，请使用小写字母、数字和下划线", name);
+        return;
+    }
+
+    let mut ctx = Context::new();
+    ctx.insert("name", name);
+    ctx.insert("method", method.as_str());
+    ctx.insert("handler", method.handler());
+
+    println!("🍺 创建API: {}", name);
+    build_slice(w, app_dir, name, "", &ctx, tera);
+}
+
+pub fn entity() -> Tera {
+    let mut tera = Tera::default();
+    // 使用 include_str! 宏将模板文件嵌入到二进制文件中
+    tera.add_raw_templates(vec![(
+        "model",
+        include_str!("../../template/entity/model.tera"),
+    )])
+    .unwrap();
+    tera
+}
+
+// 已存在时：默认提示后退出（返回 false），--skip-existing 跳过，--force 覆盖
+pub fn build_entity(w: &Writer, internal_dir: &Path, tables: &[Table], tera: Tera) -> bool {
+    let entity_dir = internal_dir.join("entity");
+
+    let paths = tables
+        .iter()
+        .map(|v| entity_dir.join(format!("{}.rs", v.module)))
+        .collect::<Vec<_>>();
+    if !w.check(&paths) {
+        return false;
+    }
+
+    for (table, path) in tables.iter().zip(paths) {
+        if w.exists(&path) && w.policy() == Policy::Skip {
+            println!("跳过已存在的Entity：{}", table.name);
+            continue;
+        }
+
+        let mut ctx = Context::new();
+        ctx.insert("table", table);
+        ctx.insert("derive_eq", &table.derive_eq());
+
+        println!("🍺 创建Entity: {}", table.name);
+
+        w.write(&path, &tera.render("model", &ctx).unwrap());
+        register_mod(w, &entity_dir.join("mod.rs"), &table.module);
+        append_line(
+            w,
+            &entity_dir.join("prelude.rs"),
+            &format!(
+                "pub use super::{}::Entity as {};",
+                table.module, table.entity
+            ),
+        );
+        println!("internal/entity/{}.rs", table.module);
+    }
+
+    // 注册模块
+    register_mod(w, &entity_dir.join("mod.rs"), "prelude");
+    register_mod(w, &internal_dir.join("lib.rs"), "entity");
+    true
+}
+
+pub fn build_crud(w: &Writer, app_dir: &Path, table: &Table, tera: &Tera) {
+    let pk = match table.primary_key() {
+        Some(v) => v,
+        None => {
+            println!("👿 数据表({})没有单一主键，跳过CRUD生成", table.name);
+            return;
+        }
+    };
+
+    // 新增/更新字段（排除自增主键）
+    let fields = table
+        .columns
+        .iter()
+        .filter(|v| !(v.primary_key && v.auto_increment))
+        .collect::<Vec<_>>();
+
+    // 需要从 sea_orm::prelude 引入的类型
+    let mut prelude_types = Vec::new();
+    for v in fields.iter().map(|v| &v.rust_type).chain([&pk.rust_type]) {
+        if PRELUDE_TYPES.contains(&v.as_str()) && !prelude_types.contains(v) {
+            prelude_types.push(v.clone());
+        }
+    }
+    prelude_types.sort();
+
+    let mut ctx = Context::new();
+    ctx.insert("table", table);
+    ctx.insert("pk", pk);
+    ctx.insert("pk_column", &camel_case(pk.field.trim_start_matches("r#")));
+    ctx.insert("fields", &fields);
+    ctx.insert("prelude_types", &prelude_types);
+    ctx.insert(
+        "has_default",
+        &fields.iter().any(|v| v.has_default && !v.nullable),
+    );
+
+    println!("🍺 创建CRUD: {}", table.name);
+    build_slice(w, app_dir, &table.module, "crud_", &ctx, tera);
+}
+
+pub fn migration() -> Tera {
+    let mut tera = Tera::default();
+    // 使用 include_str! 宏将模板文件嵌入到二进制文件中
+    tera.add_raw_templates(vec![(
+        "migration",
+        include_str!("../../template/migration/migration.tera"),
+    )])
+    .unwrap();
+    tera
+}
+
+pub fn build_migration(w: &Writer, internal_dir: &Path, name: &str, tera: Tera) {
+    if !is_ident(name) {
+        println!("👿 名称({})不合法，请使用小写字母、数字和下划线", name);
+        return;
+    }
+
+    let migration_dir = internal_dir.join("migration");
+    let mod_file = migration_dir.join("mod.rs");
+    let content = match w.read(&mod_file) {
+        Some(v) => v,
+        None => {
+            println!("👿 文件({:?})不存在，请确认！", mod_file);
+            return;
+        }
+    };
+
+    // 文件名：mYYYYMMDD_HHMMSS_<name>（UTC时间，保证按创建顺序执行）
+    let now = time::OffsetDateTime::now_utc();
+    let module = format!(
+        "m{:04}{:02}{:02}_{:02}{:02}{:02}_{}",
+        now.year(),
+        now.month() as u8,
+        now.day(),
+        now.hour(),
+        now.minute(),
+        now.second(),
+        name
+    );
+
+    // 注册迁移
+    let content = insert_before_marker(&content, MIGRATION_MOD_MARKER, &format!("mod {};", module))
+        .and_then(|v| {
+            insert_before_marker(
+                &v,
+                MIGRATION_MARKER,
+                &format!("Box::new({}::Migration),", module),
+            )
+        });
+    let content = match content {
+        Some(v) => v,
+        None => {
+            println!(
+                "👿 未找到迁移插入标记({}、{})，请确认！",
+                MIGRATION_MOD_MARKER, MIGRATION_MARKER
+            );
+            return;
+        }
+    };
+
+    println!("🍺 创建Migration: {}", name);
+
+    let path = migration_dir.join(format!("{}.rs", module));
+    w.write(&path, &tera.render("migration", &Context::new()).unwrap());
+    println!("internal/migration/{}.rs", module);
+
+    w.write(&mod_file, &content);
+    println!("internal/migration/mod.rs");
+}
+
+// 生成 api、service 并注册路由
+// API 对应的文件：api/<name>.rs、service/<name>.rs
+pub fn slice_files(app_dir: &Path, name: &str) -> [PathBuf; 2] {
+    [
+        app_dir.join("api").join(format!("{}.rs", name)),
+        app_dir.join("service").join(format!("{}.rs", name)),
+    ]
+}
+
+fn build_slice(w: &Writer, app_dir: &Path, name: &str, prefix: &str, ctx: &Context, tera: &Tera) {
+    let [api_file, service_file] = slice_files(app_dir, name);
+    // 已存在时：默认退出，--skip-existing 跳过，--force 覆盖（不重复注册路由）
+    let existed = w.exists(&api_file) || w.exists(&service_file);
+    if existed {
+        match w.policy() {
+            Policy::Abort => {
+                w.check([&api_file, &service_file]);
+                return;
+            }
+            Policy::Skip => {
+                println!("跳过已存在的API：{}", name);
+                return;
+            }
+            Policy::Force => {}
+        }
+    }
+
+    // 先渲染全部模板（用户模板可能有误），失败时不写入任何文件
+    let slice = match render_slice(prefix, ctx, tera) {
+        Ok(v) => v,
+        Err(e) => {
+            println!("👿 {}", e);
+            return;
+        }
+    };
+
+    // 查找路由插入标记
+    let router_file = match find_marker(w, &app_dir.join("router"), ROUTE_MARKER) {
+        Some(v) => v,
+        None => {
+            println!(
+                "👿 未找到路由插入标记({})，请手动注册路由：\n{}",
+                ROUTE_MARKER,
+                slice.route.trim()
+            );
+            return;
+        }
+    };
+
+    // api
+    w.write(&api_file, &slice.api);
+    register_mod(w, &app_dir.join("api").join("mod.rs"), name);
+    println!("api/{}.rs", name);
+
+    // service
+    w.write(&service_file, &slice.service);
+    register_mod(w, &app_dir.join("service").join("mod.rs"), name);
+    println!("service/{}.rs", name);
+
+    // 覆盖时路由已注册
+    if existed {
+        println!("👿 路由已注册，请确认：\n{}", slice.route.trim());
+        return;
+    }
+
+    // router
+    if let Some(route_fn) = &slice.route_fn {
+        let path = app_dir.join("router").join("route.rs");
+        let mut content = w.read(&path).unwrap();
+        content.push_str(route_fn);
+        w.write(&path, &content);
+    }
+    let content = w.read(&router_file).unwrap();
+    let content = insert_before_marker(&content, ROUTE_MARKER, slice.route.trim_end()).unwrap();
+    w.write(&router_file, &content);
+    println!(
+        "router/{}",
+        router_file.file_name().unwrap().to_string_lossy()
+    );
+}
+
+// API 的渲染结果
+struct Slice {
+    route: String,
+    api: String,
+    service: String,
+    // 路由函数（router/route.rs），部分框架没有
+    route_fn: Option<String>,
+}
+
+fn render_slice(prefix: &str, ctx: &Context, tera: &Tera) -> Result<Slice, String> {
+    let render = |v: &str| template::render(tera, &format!("{}{}", prefix, v), ctx);
+    let route_fn = format!("{}route_fn", prefix);
+    Ok(Slice {
+        route: render("route")?,
+        api: render("api")?,
+        service: render("service")?,
+        route_fn: if tera.get_template_names().any(|v| v == route_fn) {
+            Some(render("route_fn")?)
+        } else {
+            None
+        },
+    })
+}
+
+// 在目录中查找包含插入标记的文件
+fn find_marker(w: &Writer, dir: &Path, marker: &str) -> Option<std::path::PathBuf> {
+    let mut entries = fs::read_dir(dir)
+        .ok()?
+        .filter_map(|v| v.ok())
+        .map(|v| v.path())
+        .filter(|v| v.extension().is_some_and(|ext| ext == "rs"))
+        .collect::<Vec<_>>();
+    entries.sort();
+    entries
+        .into_iter()
+        .find(|v| w.read(v).is_some_and(|s| s.contains(marker)))
+}
+
+// 在标记所在行之前插入代码（保持与标记相同的缩进）
+pub fn insert_before_marker(content: &str, marker: &str, code: &str) -> Option<String> {
+    let mut out = String::with_capacity(content.len() + code.len() + 1);
+    let mut found = false;
+    for v in content.split_inclusive('\n') {
+        if !found && v.contains(marker) {
+            let indent = &v[..v.len() - v.trim_start().len()];
+            for line in code.lines() {
+                out.push_str(indent);
+                out.push_str(line);
+                out.push('\n');
+            }
+            found = true;
+        }
+        out.push_str(v);
+    }
+    found.then_some(out)
+}
+
+// 注册模块，如：pub mod greeter;
+pub fn register_mod(w: &Writer, path: &Path, name: &str) {
+    append_line(w, path, &format!("pub mod {};", name));
+}
+
+// 追加一行（已存在则忽略）
+pub fn append_line(w: &Writer, path: &Path, line: &str) {
+    let mut content = w.read(path).unwrap_or_default();
+    if content.lines().any(|v| v.trim() == line) {
+        return;
+    }
+    if !content.is_empty() && !content.ends_with('\n') {
+        content.push('\n');
+    }
+    content.push_str(line);
+    content.push('\n');
+    w.write(path, &content);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::internal::{
+        framework, render, temp_dir,
+        template::Template,
+        writer::{Policy, Writer},
+        AppMode, Db, Options,
+    };
+
+    // 生成项目（salvo），返回项目根目录
+    fn project(name: &str, apps: &[String]) -> PathBuf {
+        let root = temp_dir(name);
+        let template = Template::load(framework::select(None, false), &[]).unwrap();
+        let opts = Options::new(Db::Mysql, &[], false);
+        for (path, content) in render("demo", apps, &template, opts).unwrap() {
+            let path = root.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+        root
+    }
+
+    fn gen_tera() -> Tera {
+        framework::gen(framework::select(None, false))
+    }
+
+    fn read(path: &Path) -> String {
+        fs::read_to_string(path).unwrap()
+    }
+
+    #[test]
+    fn insert_marker() {
+        let content = "fn open() {\n    Router::new()\n        // @yiirs:routes\n}\n";
+        let v = insert_before_marker(content, ROUTE_MARKER, ".push(a())\n.push(b())").unwrap();
+        assert_eq!(
+            v,
+            "fn open() {\n    Router::new()\n        .push(a())\n        .push(b())\n        // @yiirs:routes\n}\n"
+        );
+        // 未找到标记
+        assert!(insert_before_marker("fn open() {}\n", ROUTE_MARKER, ".push(a())").is_none());
+    }
+
+    #[test]
+    fn register_mod_twice() {
+        let root = temp_dir("gen-register-mod");
+        let path = root.join("mod.rs");
+        fs::write(&path, "pub mod greeter;").unwrap();
+        let w = Writer::new(&root, false, Policy::Abort);
+        register_mod(&w, &path, "demo");
+        register_mod(&w, &path, "demo");
+        register_mod(&w, &path, "greeter");
+        assert_eq!(read(&path), "pub mod greeter;\npub mod demo;\n");
+    }
+
+    #[test]
+    fn build_api_single() {
+        let root = project("gen-api-single", &[]);
+        let app_dir =
+            root.join(Options::new(Db::Mysql, &[], false).app_dir("demo", AppMode::Single));
+        let w = Writer::new(&root, false, Policy::Force);
+        build_api(&w, &app_dir, "demo", Method::Get, &gen_tera());
+
+        assert!(app_dir.join("api/demo.rs").is_file());
+        assert!(app_dir.join("service/demo.rs").is_file());
+        let router = read(&app_dir.join("router/mod.rs"));
+        let route = router.find(".push(route::demo())").unwrap();
+        assert!(route < router.find(ROUTE_MARKER).unwrap());
+        assert!(read(&app_dir.join("router/route.rs")).contains("pub fn demo() -> Router"));
+
+        // 再次生成（--force）：模块及路由不重复注册
+        build_api(&w, &app_dir, "demo", Method::Get, &gen_tera());
+        assert_eq!(router, read(&app_dir.join("router/mod.rs")));
+        assert_eq!(
+            read(&app_dir.join("router/route.rs"))
+                .matches("pub fn demo()")
+                .count(),
+            1
+        );
+        for v in ["api/mod.rs", "service/mod.rs"] {
+            assert_eq!(read(&app_dir.join(v)).matches("pub mod demo;").count(), 1);
+        }
+    }
+
+    #[test]
+    fn build_api_multi() {
+        let apps = [String::from("foo"), String::from("bar")];
+        let root = project("gen-api-multi", &apps);
+        let opts = Options::new(Db::Mysql, &[], false);
+        let foo = root.join(opts.app_dir("foo", AppMode::Multi));
+        let bar = root.join(opts.app_dir("bar", AppMode::Multi));
+        let bar_router = read(&bar.join("router/mod.rs"));
+
+        let w = Writer::new(&root, false, Policy::Abort);
+        build_api(&w, &foo, "demo", Method::Post, &gen_tera());
+
+        assert!(foo.join("api/demo.rs").is_file());
+        assert!(read(&foo.join("router/mod.rs")).contains(".push(route::demo())"));
+        // 其他应用不受影响
+        assert!(!bar.join("api/demo.rs").exists());
+        assert_eq!(bar_router, read(&bar.join("router/mod.rs")));
+    }
+
+    #[test]
+    fn build_api_without_marker() {
+        let root = project("gen-api-marker", &[]);
+        let app_dir = root.join("src/app");
+        let router = app_dir.join("router/mod.rs");
+        fs::write(&router, read(&router).replace(ROUTE_MARKER, "")).unwrap();
+
+        let w = Writer::new(&root, false, Policy::Abort);
+        build_api(&w, &app_dir, "demo", Method::Get, &gen_tera());
+        // 未找到标记时不写入任何文件
+        assert!(!app_dir.join("api/demo.rs").exists());
+        assert!(!app_dir.join("service/demo.rs").exists());
+        assert!(!read(&app_dir.join("api/mod.rs")).contains("demo"));
+    }
+}
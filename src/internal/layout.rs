@@ -0,0 +1,534 @@
+use std::{fs, path::Path};
+
+use toml_edit::{DocumentMut, Value};
+
+use super::{cargo::CargoToml, list_files, remove_empty_dirs, writer::Writer, AppMode, Options};
+
+// 单应用的主程序路径
+pub const SINGLE_MAINFILE: &str = "src/app/main.rs";
+
+// 单应用转换为多应用：
+// src/app/* -> src/app/<name>/*（workspace 的应用代码已在 crates/<name>，无需移动）
+// config.toml -> <name>_config.toml（环境配置文件 config.<env>.toml 同理）
+// Dockerfile -> <name>.dockerfile
+// dockerun.sh -> <name>_dockerun.sh
+pub fn to_multi(w: &Writer, root: &Path, name: &str, opts: Options) -> Result<(), String> {
+    let app_dir = root.join("src/app");
+    let mut files = ["config.toml", "Dockerfile", "dockerun.sh"]
+        .into_iter()
+        .map(String::from)
+        .zip(app_files(name))
+        .collect::<Vec<_>>();
+    files.extend(profile_files(root, "config", &format!("{}_config", name)));
+
+    // 目标已存在则不转换
+    if !opts.workspace && app_dir.join(name).exists() {
+        return Err(format!(
+            "目录(src/app/{})已存在，无法转换为多应用，请确认！",
+            name
+        ));
+    }
+    for (_, to) in &files {
+        if w.exists(&root.join(to)) {
+            return Err(format!("文件({})已存在，无法转换为多应用，请确认！", to));
+        }
+    }
+
+    // 应用代码
+    if !opts.workspace {
+        for filename in list_files(&app_dir) {
+            w.rename(
+                &app_dir.join(&filename),
+                &app_dir.join(name).join(&filename),
+            );
+        }
+        remove_empty_dirs(w, &app_dir);
+        println!("src/app -> src/app/{}", name);
+    }
+
+    // 配置文件及Docker文件
+    for (from, to) in &files {
+        let path = root.join(from);
+        if w.exists(&path) {
+            w.rename(&path, &root.join(to));
+            println!("{} -> {}", from, to);
+        }
+    }
+
+    // 命令行 --config 的默认值
+    replace(
+        w,
+        root,
+        &format!("{}/cmd/mod.rs", opts.app_dir(name, AppMode::Multi)),
+        "default_value = \"config.toml\"",
+        &format!("default_value = \"{}_config.toml\"", name),
+    );
+    // 构建镜像使用 <name>.dockerfile
+    replace(
+        w,
+        root,
+        &files[2].1,
+        "docker build -t ",
+        &format!("docker build -f {}.dockerfile -t ", name),
+    );
+
+    Ok(())
+}
+
+// 按词替换：from 的首尾为名称字符时，前后不能紧邻名称字符（避免 img_foo 匹配 img_foo2）
+fn replace(w: &Writer, root: &Path, filename: &str, from: &str, to: &str) {
+    let path = root.join(filename);
+    let content = match w.read(&path) {
+        Some(v) => v,
+        None => return,
+    };
+    match replace_word(&content, from, to) {
+        Some(v) => w.write(&path, &v),
+        None => println!("👿 {} 中未找到 `{}`，请手动修改", filename, from),
+    }
+}
+
+fn replace_word(content: &str, from: &str, to: &str) -> Option<String> {
+    let is_word = |c: char| c.is_alphanumeric() || c == '_' || c == '-';
+    let check_start = from.starts_with(is_word);
+    let check_end = from.ends_with(is_word);
+
+    let mut result = String::with_capacity(content.len());
+    let mut last = 0;
+    for (i, _) in content.match_indices(from) {
+        // 与上一次匹配重叠
+        if i < last {
+            continue;
+        }
+        let end = i + from.len();
+        if (check_start && content[..i].ends_with(is_word))
+            || (check_end && content[end..].starts_with(is_word))
+        {
+            continue;
+        }
+        result.push_str(&content[last..i]);
+        result.push_str(to);
+        last = end;
+    }
+    if last == 0 {
+        return None;
+    }
+    result.push_str(&content[last..]);
+    Some(result)
+}
+
+// 链路追踪的服务名称：[otel] service_name（仅修改与原应用名称一致的配置，保留格式及注释）
+fn rename_service(w: &Writer, root: &Path, filename: &str, from: &str, to: &str) {
+    let path = root.join(filename);
+    let Some(mut doc) = w.read(&path).and_then(|v| v.parse::<DocumentMut>().ok()) else {
+        return;
+    };
+    let Some(v) = doc
+        .get_mut("otel")
+        .and_then(|v| v.get_mut("service_name"))
+        .and_then(|v| v.as_value_mut())
+        .filter(|v| v.as_str() == Some(from))
+    else {
+        return;
+    };
+    let decor = v.decor().clone();
+    *v = Value::from(to);
+    *v.decor_mut() = decor;
+    w.write(&path, &doc.to_string());
+}
+
+// 删除应用（多应用）：src/app/<name>（或 crates/<name>）、<name>_config.toml（含 <name>_config.<env>.toml）、<name>.dockerfile、<name>_dockerun.sh
+pub fn remove_app(w: &Writer, root: &Path, name: &str, opts: Options) {
+    let app_root = app_root(name, opts);
+    let app_dir = root.join(&app_root);
+    for filename in list_files(&app_dir) {
+        w.remove(&app_dir.join(filename));
+    }
+    remove_empty_dirs(w, &app_dir);
+    println!("{}", app_root);
+
+    let profiles = profile_files(root, &format!("{}_config", name), "");
+    for filename in app_files(name)
+        .into_iter()
+        .chain(profiles.into_iter().map(|(v, _)| v))
+    {
+        let path = root.join(&filename);
+        if w.exists(&path) {
+            w.remove(&path);
+            println!("{}", filename);
+        }
+    }
+}
+
+// 重命名应用（多应用）：同时修改 --config 的默认值及Docker文件中的应用名称
+pub fn rename_app(
+    w: &Writer,
+    root: &Path,
+    from: &str,
+    to: &str,
+    opts: Options,
+) -> Result<(), String> {
+    let (from_root, to_root) = (app_root(from, opts), app_root(to, opts));
+    let mut files = app_files(from)
+        .into_iter()
+        .zip(app_files(to))
+        .collect::<Vec<_>>();
+    files.extend(profile_files(
+        root,
+        &format!("{}_config", from),
+        &format!("{}_config", to),
+    ));
+
+    // 目标已存在则不重命名
+    if root.join(&to_root).exists() {
+        return Err(format!("目录({})已存在，请确认！", to_root));
+    }
+    for (_, v) in &files {
+        if w.exists(&root.join(v)) {
+            return Err(format!("文件({})已存在，请确认！", v));
+        }
+    }
+
+    // 应用代码
+    for filename in list_files(&root.join(&from_root)) {
+        w.rename(
+            &root.join(&from_root).join(&filename),
+            &root.join(&to_root).join(&filename),
+        );
+    }
+    remove_empty_dirs(w, &root.join(&from_root));
+    println!("{} -> {}", from_root, to_root);
+
+    // workspace 成员的 package 名称
+    if opts.workspace {
+        let mut cargo = CargoToml::load(w, &root.join(&to_root))?;
+        cargo.rename_package(to);
+        cargo.save(w);
+    }
+
+    // 配置文件及Docker文件
+    for (a, b) in &files {
+        let path = root.join(a);
+        if w.exists(&path) {
+            w.rename(&path, &root.join(b));
+            println!("{} -> {}", a, b);
+        }
+    }
+
+    // 命令行 --config 的默认值
+    replace(
+        w,
+        root,
+        &format!("{}/cmd/mod.rs", opts.app_dir(to, AppMode::Multi)),
+        &format!("default_value = \"{}\"", files[0].0),
+        &format!("default_value = \"{}\"", files[0].1),
+    );
+    // 链路追踪的服务名称（含环境配置文件）
+    for (_, v) in files.iter().filter(|v| v.1.ends_with(".toml")) {
+        rename_service(w, root, v, from, to);
+    }
+    // 镜像中的可执行文件
+    replace(
+        w,
+        root,
+        &files[1].1,
+        &format!("/release/{}", from),
+        &format!("/release/{}", to),
+    );
+    replace(
+        w,
+        root,
+        &files[1].1,
+        &format!("[\"./{}\"]", from),
+        &format!("[\"./{}\"]", to),
+    );
+    if opts.workspace {
+        replace(
+            w,
+            root,
+            &files[1].1,
+            &format!("-p {}", from),
+            &format!("-p {}", to),
+        );
+    }
+    // 镜像及容器名称
+    replace(
+        w,
+        root,
+        &files[2].1,
+        &format!("-f {}", files[1].0),
+        &format!("-f {}", files[1].1),
+    );
+    replace(
+        w,
+        root,
+        &files[2].1,
+        &format!("img_{}", from),
+        &format!("img_{}", to),
+    );
+    replace(
+        w,
+        root,
+        &files[2].1,
+        &format!("app_{}", from),
+        &format!("app_{}", to),
+    );
+
+    Ok(())
+}
+
+// 应用目录（多应用）：src/app/<name>，workspace 为 crates/<name>
+fn app_root(name: &str, opts: Options) -> String {
+    if opts.workspace {
+        format!("crates/{}", name)
+    } else {
+        format!("src/app/{}", name)
+    }
+}
+
+// 应用的配置文件及Docker文件（多应用）
+fn app_files(name: &str) -> [String; 3] {
+    [
+        format!("{}_config.toml", name),
+        format!("{}.dockerfile", name),
+        format!("{}_dockerun.sh", name),
+    ]
+}
+
+// 环境配置文件：<from>.<env>.toml -> <to>.<env>.toml
+fn profile_files(root: &Path, from: &str, to: &str) -> Vec<(String, String)> {
+    let entries = match fs::read_dir(root) {
+        Ok(v) => v,
+        Err(_) => return Vec::new(),
+    };
+    let mut files = entries
+        .filter_map(|v| v.ok())
+        .filter_map(|v| v.file_name().into_string().ok())
+        .filter_map(|v| {
+            let env = v
+                .strip_prefix(from)?
+                .strip_prefix('.')?
+                .strip_suffix(".toml")?;
+            if env.is_empty() || env.contains('.') {
+                return None;
+            }
+            let to = format!("{}.{}.toml", to, env);
+            Some((v, to))
+        })
+        .collect::<Vec<_>>();
+    files.sort();
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::internal::{temp_dir, writer::Policy, Db};
+
+    fn project(name: &str, files: &[(&str, &str)]) -> PathBuf {
+        let root = temp_dir(name);
+        for (path, content) in files {
+            let path = root.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+        root
+    }
+
+    fn read(root: &Path, path: &str) -> String {
+        fs::read_to_string(root.join(path)).unwrap()
+    }
+
+    #[test]
+    fn to_multi() {
+        let root = project(
+            "layout-to-multi",
+            &[
+                ("src/app/main.rs", "fn main() {}"),
+                (
+                    "src/app/cmd/mod.rs",
+                    "#[arg(short, long, default_value = \"config.toml\")]",
+                ),
+                ("config.toml", "[app]"),
+                ("config.dev.toml", "[app]"),
+                ("Dockerfile", "FROM rust"),
+                ("dockerun.sh", "docker build -t img_demo ."),
+            ],
+        );
+        let w = Writer::new(&root, false, Policy::Abort);
+        let opts = Options::new(Db::Mysql, &[], false);
+        super::to_multi(&w, &root, "demo", opts).unwrap();
+
+        assert!(!root.join("src/app/main.rs").exists());
+        assert_eq!(read(&root, "src/app/demo/main.rs"), "fn main() {}");
+        assert_eq!(
+            read(&root, "src/app/demo/cmd/mod.rs"),
+            "#[arg(short, long, default_value = \"demo_config.toml\")]"
+        );
+        assert!(root.join("demo_config.toml").is_file());
+        assert!(root.join("demo_config.dev.toml").is_file());
+        assert!(root.join("demo.dockerfile").is_file());
+        assert_eq!(
+            read(&root, "demo_dockerun.sh"),
+            "docker build -f demo.dockerfile -t img_demo ."
+        );
+        for v in [
+            "config.toml",
+            "config.dev.toml",
+            "Dockerfile",
+            "dockerun.sh",
+        ] {
+            assert!(!root.join(v).exists(), "{}", v);
+        }
+    }
+
+    #[test]
+    fn to_multi_conflict() {
+        let root = project(
+            "layout-to-multi-conflict",
+            &[
+                ("src/app/main.rs", "fn main() {}"),
+                ("config.toml", "[app]"),
+                ("demo_config.toml", "[app]"),
+            ],
+        );
+        let w = Writer::new(&root, false, Policy::Abort);
+        let opts = Options::new(Db::Mysql, &[], false);
+        assert!(super::to_multi(&w, &root, "demo", opts).is_err());
+        // 不做任何修改
+        assert!(root.join("src/app/main.rs").is_file());
+        assert!(root.join("config.toml").is_file());
+    }
+
+    #[test]
+    fn replace_word() {
+        let content = "docker rm -f app_foo\ndocker rm -f app_foo2\n-v /data/app_foo:/data";
+        assert_eq!(
+            super::replace_word(content, "app_foo", "app_bar").unwrap(),
+            "docker rm -f app_bar\ndocker rm -f app_foo2\n-v /data/app_bar:/data"
+        );
+        // 首尾不是名称字符时不限制前后
+        assert_eq!(
+            super::replace_word("COPY target/release/foo .", "/release/foo", "/release/bar")
+                .unwrap(),
+            "COPY target/release/bar ."
+        );
+        assert!(super::replace_word("img_foo2", "img_foo", "img_bar").is_none());
+    }
+
+    #[test]
+    fn rename_app() {
+        let root = project(
+            "layout-rename-app",
+            &[
+                ("src/app/foo/main.rs", "fn main() {}"),
+                (
+                    "src/app/foo/cmd/mod.rs",
+                    "#[arg(short, long, default_value = \"foo_config.toml\")]",
+                ),
+                (
+                    "foo_config.toml",
+                    "[app]\nname = \"foo\"\n\n[otel]\nservice_name = \"foo\" # 服务名称\n",
+                ),
+                ("foo_config.dev.toml", "[otel]\nservice_name = \"foo2\"\n"),
+                (
+                    "foo.dockerfile",
+                    "COPY --from=builder /project/target/release/foo .\nENTRYPOINT [\"./foo\"]\n",
+                ),
+                (
+                    "foo_dockerun.sh",
+                    "docker rm -f app_foo\ndocker rmi -f img_foo\ndocker rmi -f img_foo2\ndocker build -f foo.dockerfile -t img_foo .\n",
+                ),
+                // 名称为前缀的其他应用不受影响
+                ("src/app/foo2/main.rs", "fn main() {}"),
+                ("foo2_config.toml", "[otel]\nservice_name = \"foo2\"\n"),
+            ],
+        );
+        let w = Writer::new(&root, false, Policy::Abort);
+        let opts = Options::new(Db::Mysql, &[], false);
+        super::rename_app(&w, &root, "foo", "bar", opts).unwrap();
+
+        assert!(!root.join("src/app/foo").exists());
+        assert_eq!(read(&root, "src/app/bar/main.rs"), "fn main() {}");
+        assert_eq!(
+            read(&root, "src/app/bar/cmd/mod.rs"),
+            "#[arg(short, long, default_value = \"bar_config.toml\")]"
+        );
+        assert_eq!(
+            read(&root, "bar_config.toml"),
+            "[app]\nname = \"foo\"\n\n[otel]\nservice_name = \"bar\" # 服务名称\n"
+        );
+        assert_eq!(
+            read(&root, "bar_config.dev.toml"),
+            "[otel]\nservice_name = \"foo2\"\n"
+        );
+        assert_eq!(
+            read(&root, "bar.dockerfile"),
+            "COPY --from=builder /project/target/release/bar .\nENTRYPOINT [\"./bar\"]\n"
+        );
+        assert_eq!(
+            read(&root, "bar_dockerun.sh"),
+            "docker rm -f app_bar\ndocker rmi -f img_bar\ndocker rmi -f img_foo2\ndocker build -f bar.dockerfile -t img_bar .\n"
+        );
+        for v in [
+            "foo_config.toml",
+            "foo_config.dev.toml",
+            "foo.dockerfile",
+            "foo_dockerun.sh",
+        ] {
+            assert!(!root.join(v).exists(), "{}", v);
+        }
+        assert!(root.join("src/app/foo2/main.rs").is_file());
+        assert_eq!(
+            read(&root, "foo2_config.toml"),
+            "[otel]\nservice_name = \"foo2\"\n"
+        );
+    }
+
+    #[test]
+    fn rename_app_conflict() {
+        let root = project(
+            "layout-rename-app-conflict",
+            &[
+                ("src/app/foo/main.rs", "fn main() {}"),
+                ("foo_config.toml", ""),
+                ("bar_config.toml", ""),
+            ],
+        );
+        let w = Writer::new(&root, false, Policy::Abort);
+        let opts = Options::new(Db::Mysql, &[], false);
+        assert!(super::rename_app(&w, &root, "foo", "bar", opts).is_err());
+        assert!(root.join("src/app/foo/main.rs").is_file());
+        assert!(root.join("foo_config.toml").is_file());
+    }
+
+    #[test]
+    fn profile_files() {
+        let root = project(
+            "layout-profile-files",
+            &[
+                ("config.toml", ""),
+                ("config.dev.toml", ""),
+                ("config.prod.toml", ""),
+                ("config.a.b.toml", ""),
+                ("config..toml", ""),
+                ("configx.dev.toml", ""),
+            ],
+        );
+        assert_eq!(
+            super::profile_files(&root, "config", "demo_config"),
+            vec![
+                (
+                    "config.dev.toml".to_string(),
+                    "demo_config.dev.toml".to_string()
+                ),
+                (
+                    "config.prod.toml".to_string(),
+                    "demo_config.prod.toml".to_string()
+                ),
+            ]
+        );
+    }
+}
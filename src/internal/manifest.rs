@@ -0,0 +1,162 @@
+use std::{fs, path::Path, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use super::{
+    framework::{self, Framework},
+    writer::Writer,
+    AppMode, Db, Options,
+};
+
+// 项目清单文件（项目根目录）
+pub const MANIFEST: &str = "yiirs.toml";
+
+const HEADER: &str = "# 由 yiirs 生成，记录项目的生成方式，后续命令（yiirs app、yiirs gen 等）据此生成代码，请勿随意修改\n\n";
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Manifest {
+    // 生成项目的 yiirs 版本（即模板版本）
+    pub version: String,
+    // 项目名称（单应用的应用名称）
+    #[serde(default)]
+    pub name: String,
+    pub framework: String,
+    pub mode: AppMode,
+    // 应用列表（多应用）
+    #[serde(default)]
+    pub apps: Vec<String>,
+    pub db: Db,
+    pub redis: bool,
+    pub crypto: bool,
+    pub lock: bool,
+    // Cargo workspace 结构
+    #[serde(default)]
+    pub workspace: bool,
+    // 自定义模板目录
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub template_dirs: Vec<PathBuf>,
+}
+
+impl Manifest {
+    pub fn new(
+        name: &str,
+        framework: &dyn Framework,
+        mode: AppMode,
+        apps: &[String],
+        opts: Options,
+        template_dirs: &[PathBuf],
+    ) -> Self {
+        Manifest {
+            version: String::from(env!("CARGO_PKG_VERSION")),
+            name: name.to_string(),
+            framework: String::from(framework.name()),
+            mode,
+            apps: apps.to_vec(),
+            db: opts.db,
+            redis: opts.redis,
+            crypto: opts.crypto,
+            lock: opts.lock,
+            workspace: opts.workspace,
+            // 记录绝对路径，便于在项目目录中执行后续命令
+            template_dirs: template_dirs
+                .iter()
+                .map(|v| v.canonicalize().unwrap_or(v.clone()))
+                .collect(),
+        }
+    }
+
+    // 旧项目没有 yiirs.toml，返回 None
+    pub fn load(root: &Path) -> Result<Option<Self>, String> {
+        let path = root.join(MANIFEST);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content =
+            fs::read_to_string(&path).map_err(|e| format!("读取文件({:?})失败：{}", path, e))?;
+        let manifest =
+            toml::from_str(&content).map_err(|e| format!("解析文件({:?})失败：{}", path, e))?;
+        Ok(Some(manifest))
+    }
+
+    pub fn save(&self, w: &Writer, root: &Path) {
+        let content = toml::to_string(self).unwrap();
+        w.write(&root.join(MANIFEST), &format!("{}{}", HEADER, content));
+    }
+
+    pub fn options(&self) -> Options {
+        Options {
+            db: self.db,
+            redis: self.redis,
+            crypto: self.crypto,
+            lock: self.lock,
+            workspace: self.workspace,
+        }
+    }
+
+    // 命令行指定的框架须与项目一致
+    pub fn framework(
+        &self,
+        framework: Option<&'static dyn Framework>,
+    ) -> Result<&'static dyn Framework, String> {
+        let v = match framework::find(&self.framework) {
+            Some(v) => v,
+            None => return Err(format!("不支持的框架({})", self.framework)),
+        };
+        match framework {
+            Some(f) if f.name() != v.name() => Err(format!(
+                "项目使用的框架为 {}（{}），请确认！",
+                v.name(),
+                MANIFEST
+            )),
+            _ => Ok(v),
+        }
+    }
+
+    // 命令行指定的模板目录在项目记录之后加载
+    pub fn template_dirs(&self, dirs: Vec<PathBuf>) -> Vec<PathBuf> {
+        let mut v = self.template_dirs.clone();
+        v.extend(dirs);
+        v
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::internal::{temp_dir, writer::Policy, Component};
+
+    #[test]
+    fn save_and_load() {
+        let root = temp_dir("manifest-save");
+        let opts = Options::new(Db::Postgres, &[Component::Redis], true);
+        let framework = framework::find("axum").unwrap();
+        let apps = vec![String::from("foo")];
+        let m = Manifest::new("demo", framework, AppMode::Multi, &apps, opts, &[]);
+        m.save(&Writer::new(&root, false, Policy::Abort), &root);
+
+        let m = Manifest::load(&root).unwrap().unwrap();
+        assert_eq!(m.options(), opts);
+        assert_eq!(m.apps, apps);
+        assert!(m.framework(None).is_ok());
+        assert!(m.framework(framework::find("salvo")).is_err());
+    }
+
+    #[test]
+    fn load_legacy() {
+        let root = temp_dir("manifest-legacy");
+        assert!(Manifest::load(&root).unwrap().is_none());
+
+        // 早期版本没有 name、workspace
+        fs::write(
+            root.join(MANIFEST),
+            "version = \"0.9.0\"\nframework = \"salvo\"\nmode = \"Single\"\ndb = \"mysql\"\nredis = true\ncrypto = true\nlock = true\n",
+        )
+        .unwrap();
+        let m = Manifest::load(&root).unwrap().unwrap();
+        assert!(m.name.is_empty());
+        assert!(!m.workspace);
+
+        fs::write(root.join(MANIFEST), "version = 1").unwrap();
+        assert!(Manifest::load(&root).is_err());
+    }
+}
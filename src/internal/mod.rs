@@ -1,20 +1,129 @@
+pub mod actix;
 pub mod axum;
+pub mod cargo;
+pub mod common;
+pub mod framework;
+pub mod gen;
+pub mod layout;
+pub mod manifest;
+pub mod poem;
 pub mod salvo;
+pub mod schema;
+pub mod secrets;
+pub mod template;
+pub mod upgrade;
+pub mod writer;
 
-use std::{
-    fs::{self, File},
-    io::{self, Write},
-    path::Path,
-};
+use std::{fs, io, path::Path};
 
 use tera::Context;
 
-#[derive(serde::Serialize)]
+use template::Template;
+use writer::Writer;
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy, Debug, PartialEq)]
 pub enum AppMode {
     Single,
     Multi,
 }
 
+#[derive(clap::ValueEnum, serde::Serialize, serde::Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Db {
+    Mysql,
+    Postgres,
+    Sqlite,
+    None,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum Component {
+    Db,
+    Redis,
+    Crypto,
+    Lock,
+}
+
+// 项目选项：数据库、可选组件及项目结构
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Options {
+    pub db: Db,
+    pub redis: bool,
+    pub crypto: bool,
+    pub lock: bool,
+    // Cargo workspace：crates/internal 及每个应用一个 crate
+    pub workspace: bool,
+}
+
+impl Options {
+    pub fn new(db: Db, without: &[Component], workspace: bool) -> Self {
+        let mut opts = Options {
+            db,
+            redis: true,
+            crypto: true,
+            lock: true,
+            workspace,
+        };
+        for v in without {
+            match v {
+                Component::Db => opts.db = Db::None,
+                Component::Redis => opts.redis = false,
+                Component::Crypto => opts.crypto = false,
+                Component::Lock => opts.lock = false,
+            }
+        }
+        // 分布式锁基于Redis
+        if !opts.redis {
+            opts.lock = false;
+        }
+        opts
+    }
+
+    fn insert_into(&self, ctx: &mut Context) {
+        ctx.insert("db", &self.db);
+        ctx.insert("redis", &self.redis);
+        ctx.insert("crypto", &self.crypto);
+        ctx.insert("lock", &self.lock);
+        ctx.insert("workspace", &self.workspace);
+    }
+
+    // 内部库的源码目录（相对项目根目录）
+    pub fn internal_dir(&self) -> &'static str {
+        if self.workspace {
+            "crates/internal/src"
+        } else {
+            "src/internal"
+        }
+    }
+
+    // 应用的源码目录（相对项目根目录）
+    pub fn app_dir(&self, name: &str, mode: AppMode) -> String {
+        match (self.workspace, mode) {
+            (true, _) => format!("crates/{}/src", name),
+            (false, AppMode::Single) => String::from("src/app"),
+            (false, AppMode::Multi) => format!("src/app/{}", name),
+        }
+    }
+
+    pub fn app(&self, name: &str, mode: AppMode) -> App {
+        App {
+            name: name.to_string(),
+            mainfile: format!("{}/main.rs", self.app_dir(name, mode)),
+        }
+    }
+
+    // 未启用组件的文件不生成
+    fn excluded(&self, filename: &str) -> bool {
+        match filename {
+            "core/db.rs" | "migration/mod.rs" | "cmd/migrate.rs" => self.db == Db::None,
+            "core/cache.rs" | "core/manager.rs" => !self.redis,
+            "crypto/aes.rs" | "core/secrets.rs" | "cmd/secrets.rs" => !self.crypto,
+            "util/mutex.rs" => !self.lock,
+            _ => false,
+        }
+    }
+}
+
 #[derive(serde::Serialize)]
 pub struct App {
     pub name: String,
@@ -31,191 +140,339 @@ pub fn is_empty_dir(path: &Path) -> bool {
     }
 }
 
-pub fn build_axum_project(root: &Path, name: &str, apps: &Vec<String>) {
-    let template = (axum::global(), axum::internal());
-    let (mode, bins) = build_project(root, name, apps, template);
-    build_app(
-        root,
-        &bins,
-        mode,
-        (axum::app(), axum::docker(), axum::other()),
-    );
+// 目录下的所有文件（相对路径，以 / 分隔）
+pub fn list_files(dir: &Path) -> Vec<String> {
+    let mut files = Vec::new();
+    let mut dirs = vec![dir.to_path_buf()];
+    while let Some(v) = dirs.pop() {
+        let entries = match fs::read_dir(&v) {
+            Ok(v) => v,
+            Err(_) => continue,
+        };
+        for entry in entries.filter_map(|v| v.ok()) {
+            let path = entry.path();
+            if path.is_dir() {
+                dirs.push(path);
+            } else if let Ok(v) = path.strip_prefix(dir) {
+                let v = v
+                    .components()
+                    .map(|c| c.as_os_str().to_string_lossy())
+                    .collect::<Vec<_>>();
+                files.push(v.join("/"));
+            }
+        }
+    }
+    files.sort();
+    files
 }
 
-pub fn build_axum_app(root: &Path, apps: &Vec<App>, mode: AppMode) {
-    build_app(
-        root,
-        apps,
-        mode,
-        (axum::app(), axum::docker(), axum::other()),
-    );
+// 删除移动或删除文件后留下的空目录
+pub fn remove_empty_dirs(w: &Writer, dir: &Path) {
+    if w.dry_run() {
+        return;
+    }
+    let entries = match fs::read_dir(dir) {
+        Ok(v) => v,
+        Err(_) => return,
+    };
+    for entry in entries.filter_map(|v| v.ok()) {
+        if entry.path().is_dir() {
+            remove_empty_dirs(w, &entry.path());
+        }
+    }
+    // 非空目录删除失败，忽略
+    let _ = fs::remove_dir(dir);
 }
 
-pub fn build_salvo_project(root: &Path, name: &str, apps: &Vec<String>) {
-    let template = (salvo::global(), salvo::internal());
-    let (mode, bins) = build_project(root, name, apps, template);
-    build_app(
-        root,
-        &bins,
-        mode,
-        (salvo::app(), salvo::docker(), salvo::other()),
-    );
+// 渲染结果：(相对项目根目录的路径, 内容)
+pub type Rendered = Vec<(String, String)>;
+
+// 已存在的文件按写入策略处理，Abort 时不写入任何文件并返回 false
+pub fn build_project(
+    w: &Writer,
+    root: &Path,
+    name: &str,
+    apps: &[String],
+    template: &Template,
+    opts: Options,
+) -> bool {
+    let (mode, bins) = bins(name, apps, opts);
+
+    let rendered = render_project(name, mode, &bins, template, opts)
+        .and_then(|files| Ok((files, render_apps(&bins, mode, template, opts)?)));
+    let (files, app_files) = match rendered {
+        Ok(v) => v,
+        Err(e) => {
+            println!("👿 {}", e);
+            return false;
+        }
+    };
+    let paths = files
+        .iter()
+        .chain(app_files.iter().flatten())
+        .map(|v| root.join(&v.0))
+        .collect::<Vec<_>>();
+    if !w.check(&paths) {
+        return false;
+    }
+
+    // 创建项目
+    println!("🍺 创建项目: {}", name);
+    write_rendered(w, root, &files);
+
+    // 创建app
+    for (app, files) in bins.iter().zip(app_files) {
+        println!("🍺 创建App: {}", &app.name);
+        write_rendered(w, root, &files);
+    }
+
+    true
 }
 
-pub fn build_salvo_app(root: &Path, apps: &Vec<App>, mode: AppMode) {
-    build_app(
-        root,
-        apps,
-        mode,
-        (salvo::app(), salvo::docker(), salvo::other()),
-    );
+// 检查应用的文件是否已存在（Abort 时提示并返回 false）
+pub fn check_app(
+    w: &Writer,
+    root: &Path,
+    apps: &[App],
+    mode: AppMode,
+    template: &Template,
+    opts: Options,
+) -> bool {
+    let app_files = match render_apps(apps, mode, template, opts) {
+        Ok(v) => v,
+        Err(e) => {
+            println!("👿 {}", e);
+            return false;
+        }
+    };
+    let paths = app_files
+        .into_iter()
+        .flatten()
+        .map(|v| root.join(v.0))
+        .collect::<Vec<_>>();
+    w.check(&paths)
 }
 
-fn build_project(
+// 已存在的文件按写入策略处理，Abort 时不写入任何文件并返回 false
+pub fn build_app(
+    w: &Writer,
     root: &Path,
-    name: &str,
-    apps: &Vec<String>,
-    template: (tera::Tera, tera::Tera),
-) -> (AppMode, Vec<App>) {
-    let src_dir = root.join("src");
+    apps: &[App],
+    mode: AppMode,
+    template: &Template,
+    opts: Options,
+) -> bool {
+    if !check_app(w, root, apps, mode, template, opts) {
+        return false;
+    }
+    // check_app 已渲染成功
+    let Ok(app_files) = render_apps(apps, mode, template, opts) else {
+        return false;
+    };
 
-    let mut bins = Vec::<App>::new();
+    // 创建app
+    for (app, files) in apps.iter().zip(app_files) {
+        println!("🍺 创建App: {}", &app.name);
+        write_rendered(w, root, &files);
+    }
 
-    let mode = if apps.is_empty() {
-        bins.push(App {
-            name: name.to_string(),
-            mainfile: String::from("src/app/main.rs"),
-        });
-        AppMode::Single
-    } else {
-        for appname in apps {
-            bins.push(App {
-                name: appname.clone(),
-                mainfile: format!("src/app/{}/main.rs", appname),
-            });
-        }
-        AppMode::Multi
+    true
+}
+
+fn render_apps(
+    apps: &[App],
+    mode: AppMode,
+    template: &Template,
+    opts: Options,
+) -> Result<Vec<Rendered>, String> {
+    apps.iter()
+        .map(|app| render_app(app, mode, template, opts))
+        .collect()
+}
+
+// 单应用以项目名称作为应用名称
+pub fn bins(name: &str, apps: &[String], opts: Options) -> (AppMode, Vec<App>) {
+    if apps.is_empty() {
+        return (AppMode::Single, vec![opts.app(name, AppMode::Single)]);
+    }
+    let bins = apps.iter().map(|v| opts.app(v, AppMode::Multi)).collect();
+    (AppMode::Multi, bins)
+}
+
+// 渲染项目的全部文件（yiirs upgrade 及基线使用）
+pub fn render(
+    name: &str,
+    apps: &[String],
+    template: &Template,
+    opts: Options,
+) -> Result<Rendered, String> {
+    let (mode, bins) = bins(name, apps, opts);
+    let mut files = render_project(name, mode, &bins, template, opts)?;
+    files.extend(
+        render_apps(&bins, mode, template, opts)?
+            .into_iter()
+            .flatten(),
+    );
+    Ok(files)
+}
+
+// [workspace.dependencies] 中的依赖（不含 internal）
+fn workspace_dependencies(cargo: Option<&str>) -> Vec<String> {
+    let table = match cargo.and_then(|v| toml::from_str::<toml::Table>(v).ok()) {
+        Some(v) => v,
+        None => return Vec::new(),
     };
+    table
+        .get("workspace")
+        .and_then(|v| v.get("dependencies"))
+        .and_then(|v| v.as_table())
+        .map(|v| v.keys().filter(|k| *k != "internal").cloned().collect())
+        .unwrap_or_default()
+}
 
-    let (tera_global, tera_internal) = template;
+// 渲染项目文件（global、internal）
+pub fn render_project(
+    name: &str,
+    mode: AppMode,
+    bins: &Vec<App>,
+    template: &Template,
+    opts: Options,
+) -> Result<Rendered, String> {
+    let tera_global = template.global()?;
+    let tera_internal = template.internal()?;
 
     let mut ctx = Context::new();
     ctx.insert("name", &name);
     ctx.insert("apps", &bins);
     ctx.insert("mode", &mode);
+    ctx.insert("framework", template.framework().title());
+    ctx.insert("homepage", template.framework().homepage());
+    ctx.insert("dependencies", template.framework().dependencies());
+    opts.insert_into(&mut ctx);
+    template.insert_into(&mut ctx);
 
-    // 创建项目
-    println!("🍺 创建项目: {}", name);
+    let mut files = Rendered::new();
 
     // global
     for filename in tera_global.get_template_names() {
-        let content = tera_global.render(filename, &ctx).unwrap();
-        let path = root.join(filename);
-        // 创建文件
-        let mut file = File::create(path).unwrap();
-        // 将内容写入文件
-        file.write_all(content.as_bytes()).unwrap();
-        println!("internal/{}", filename)
+        let content = template::render(&tera_global, filename, &ctx)?;
+        files.push((filename.to_string(), content));
     }
 
     // internal
-    let internal_dir = src_dir.join("internal");
     for filename in tera_internal.get_template_names() {
-        let content = tera_internal.render(filename, &ctx).unwrap();
-        let path = internal_dir.join(filename);
-        if let Some(dir) = path.parent() {
-            fs::create_dir_all(dir).unwrap();
+        if opts.excluded(filename) {
+            continue;
         }
-        // 创建文件
-        let mut file = File::create(path).unwrap();
-        // 将内容写入文件
-        file.write_all(content.as_bytes()).unwrap();
-        println!("internal/{}", filename)
+        let content = template::render(&tera_internal, filename, &ctx)?;
+        files.push((format!("{}/{}", opts.internal_dir(), filename), content));
     }
 
-    (mode, bins)
+    // workspace：crates/internal/Cargo.toml，依赖为 [workspace.dependencies] 中的全部依赖
+    if opts.workspace {
+        let cargo = files
+            .iter()
+            .find(|v| v.0 == "Cargo.toml")
+            .map(|v| v.1.as_str());
+        ctx.insert("workspace_dependencies", &workspace_dependencies(cargo));
+        let content = template::render(&template.crates()?, "internal", &ctx)?;
+        files.push((String::from("crates/internal/Cargo.toml"), content));
+    }
+
+    files.sort();
+    Ok(files)
 }
 
-fn build_app(
-    root: &Path,
-    apps: &Vec<App>,
+// 渲染应用文件（app、dockerfile、config.toml）
+pub fn render_app(
+    app: &App,
     mode: AppMode,
-    template: (tera::Tera, tera::Tera, tera::Tera),
-) {
-    let (tera_app, tera_docker, tera_other) = template;
+    template: &Template,
+    opts: Options,
+) -> Result<Rendered, String> {
+    let tera_app = template.app()?;
+    let tera_docker = template.docker()?;
+    let tera_other = template.other()?;
 
-    let src_dir = root.join("src");
+    let mut ctx = Context::new();
+    ctx.insert("app_name", &app.name);
+    opts.insert_into(&mut ctx);
+    template.insert_into(&mut ctx);
 
-    // 创建app
-    for app in apps {
-        let mut ctx = Context::new();
-        ctx.insert("app_name", &app.name);
-
-        // 模式
-        let (app_dir, app_prefix) = match mode {
-            AppMode::Single => {
-                ctx.insert("dockerfile", "Dockerfile");
-                ctx.insert("cfgfile", "config.toml");
-                (src_dir.join("app"), String::from("app"))
-            }
-            AppMode::Multi => {
-                ctx.insert("dockerfile", format!("{}.dockerfile", &app.name).as_str());
-                ctx.insert("cfgfile", format!("{}_config.toml", &app.name).as_str());
-                (
-                    src_dir.join("app").join(&app.name),
-                    format!("app/{}", &app.name),
-                )
-            }
-        };
-        if !is_empty_dir(&app_dir) {
-            println!("👿 目录({:?})不为空，请确认！", app_dir);
-            return;
+    // 模式
+    match mode {
+        AppMode::Single => {
+            ctx.insert("dockerfile", "Dockerfile");
+            ctx.insert("cfgfile", "config.toml");
         }
+        AppMode::Multi => {
+            ctx.insert("dockerfile", format!("{}.dockerfile", &app.name).as_str());
+            ctx.insert("cfgfile", format!("{}_config.toml", &app.name).as_str());
+        }
+    };
+    let app_prefix = opts.app_dir(&app.name, mode);
 
-        println!("🍺 创建App: {}", &app.name);
+    let mut files = Rendered::new();
 
-        // app
-        for filename in tera_app.get_template_names() {
-            let content = tera_app.render(filename, &ctx).unwrap();
-            let path = app_dir.join(filename);
-            if let Some(dir) = path.parent() {
-                fs::create_dir_all(dir).unwrap();
-            }
-            // 创建文件
-            let mut file = File::create(path).unwrap();
-            // 将内容写入文件
-            file.write_all(content.as_bytes()).unwrap();
-            println!("{}/{}", app_prefix, filename)
+    // app
+    for filename in tera_app.get_template_names() {
+        if opts.excluded(filename) {
+            continue;
         }
+        let content = template::render(&tera_app, filename, &ctx)?;
+        files.push((format!("{}/{}", app_prefix, filename), content));
+    }
 
-        // dockerfile
-        for filename in tera_docker.get_template_names() {
-            let content = tera_docker.render(filename, &ctx).unwrap();
-            let path = match mode {
-                AppMode::Single => root.join(filename),
-                AppMode::Multi => {
-                    root.join(format!("{}.{}", &app.name, filename.to_lowercase()).as_str())
-                }
-            };
-            // 创建文件
-            let mut file = File::create(path).unwrap();
-            // 将内容写入文件
-            file.write_all(content.as_bytes()).unwrap();
-            println!("{}", filename)
-        }
+    // dockerfile
+    for filename in tera_docker.get_template_names() {
+        let content = template::render(&tera_docker, filename, &ctx)?;
+        let path = match mode {
+            AppMode::Single => filename.to_string(),
+            AppMode::Multi => format!("{}.{}", &app.name, filename.to_lowercase()),
+        };
+        files.push((path, content));
+    }
+
+    // config.toml
+    for filename in tera_other.get_template_names() {
+        let content = template::render(&tera_other, filename, &ctx)?;
+        let path = match mode {
+            AppMode::Single => filename.to_string(),
+            AppMode::Multi => format!("{}_{}", &app.name, filename),
+        };
+        files.push((path, content));
+    }
+
+    // workspace：crates/<应用>/Cargo.toml
+    if opts.workspace {
+        let deps = toml::from_str::<toml::Table>(template.framework().dependencies())
+            .map(|v| v.keys().cloned().collect::<Vec<_>>())
+            .unwrap_or_default();
+        ctx.insert("framework_dependencies", &deps);
+        let content = template::render(&template.crates()?, "app", &ctx)?;
+        files.push((format!("crates/{}/Cargo.toml", &app.name), content));
+    }
 
-        // config.toml
-        for filename in tera_other.get_template_names() {
-            let content = tera_other.render(filename, &ctx).unwrap();
-            let path = match mode {
-                AppMode::Single => root.join(filename),
-                AppMode::Multi => root.join(format!("{}_{}", &app.name, filename).as_str()),
-            };
-            // 创建文件
-            let mut file = File::create(path).unwrap();
-            // 将内容写入文件
-            file.write_all(content.as_bytes()).unwrap();
-            println!("{}", filename)
+    files.sort();
+    Ok(files)
+}
+
+// 写入文件，并保存一份到 .yiirs/base 作为 yiirs upgrade 三方合并的基线
+fn write_rendered(w: &Writer, root: &Path, files: &Rendered) {
+    for (filename, content) in files {
+        // 仅记录实际生成的文件的基线，跳过的文件保留原基线
+        if w.create(&root.join(filename), content) {
+            println!("{}", filename);
+            w.write(&root.join(upgrade::BASE_DIR).join(filename), content);
         }
     }
 }
+
+// 测试用的临时目录（已存在则清空）
+#[cfg(test)]
+pub fn temp_dir(name: &str) -> std::path::PathBuf {
+    let dir = std::env::temp_dir().join(format!("yiirs-test-{}-{}", std::process::id(), name));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
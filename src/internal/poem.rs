@@ -0,0 +1,104 @@
+use super::framework::{Framework, Templates};
+
+pub struct Poem;
+
+impl Framework for Poem {
+    fn name(&self) -> &'static str {
+        "poem"
+    }
+
+    fn title(&self) -> &'static str {
+        "poem"
+    }
+
+    fn homepage(&self) -> &'static str {
+        "https://github.com/poem-web/poem"
+    }
+
+    fn dependencies(&self) -> &'static str {
+        include_str!("../../template/poem/dependencies.tera")
+    }
+
+    fn internal(&self) -> Templates {
+        vec![
+            // middleware
+            (
+                "middleware/catch_panic.rs",
+                include_str!("../../template/poem/internal/middleware/catch_panic.tera"),
+            ),
+            (
+                "middleware/log.rs",
+                include_str!("../../template/poem/internal/middleware/log.tera"),
+            ),
+            (
+                "middleware/metrics.rs",
+                include_str!("../../template/poem/internal/middleware/metrics.tera"),
+            ),
+            (
+                "middleware/trace.rs",
+                include_str!("../../template/poem/internal/middleware/trace.tera"),
+            ),
+            // result
+            (
+                "result/code.rs",
+                include_str!("../../template/poem/internal/result/code.tera"),
+            ),
+            (
+                "result/rejection.rs",
+                include_str!("../../template/poem/internal/result/rejection.tera"),
+            ),
+            (
+                "result/reply.rs",
+                include_str!("../../template/poem/internal/result/reply.tera"),
+            ),
+        ]
+    }
+
+    fn app(&self) -> Templates {
+        vec![
+            // api
+            (
+                "api/greeter.rs",
+                include_str!("../../template/poem/app/api/greeter.tera"),
+            ),
+            (
+                "api/health.rs",
+                include_str!("../../template/poem/app/api/health.tera"),
+            ),
+            (
+                "api/metrics.rs",
+                include_str!("../../template/poem/app/api/metrics.tera"),
+            ),
+            // cmd
+            (
+                "cmd/serve.rs",
+                include_str!("../../template/poem/app/cmd/serve.tera"),
+            ),
+            // middleware
+            (
+                "middleware/auth.rs",
+                include_str!("../../template/poem/app/middleware/auth.tera"),
+            ),
+            // router
+            (
+                "router/route.rs",
+                include_str!("../../template/poem/app/router/route.tera"),
+            ),
+        ]
+    }
+
+    fn gen(&self) -> Templates {
+        vec![
+            ("api", include_str!("../../template/poem/gen/api.tera")),
+            ("route", include_str!("../../template/poem/gen/route.tera")),
+            (
+                "crud_api",
+                include_str!("../../template/poem/gen/crud_api.tera"),
+            ),
+            (
+                "crud_route",
+                include_str!("../../template/poem/gen/crud_route.tera"),
+            ),
+        ]
+    }
+}
@@ -0,0 +1,491 @@
+use sqlparser::{
+    ast::{ColumnOption, Statement, TableConstraint},
+    dialect::{Dialect, GenericDialect, MySqlDialect, PostgreSqlDialect, SQLiteDialect},
+    parser::Parser,
+};
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug)]
+pub enum SqlDialect {
+    Mysql,
+    Postgres,
+    Sqlite,
+    Generic,
+}
+
+impl SqlDialect {
+    fn dialect(&self) -> Box<dyn Dialect> {
+        match self {
+            SqlDialect::Mysql => Box::new(MySqlDialect {}),
+            SqlDialect::Postgres => Box::new(PostgreSqlDialect {}),
+            SqlDialect::Sqlite => Box::new(SQLiteDialect {}),
+            SqlDialect::Generic => Box::new(GenericDialect {}),
+        }
+    }
+}
+
+#[derive(serde::Serialize, Debug)]
+pub struct Table {
+    pub name: String,
+    pub module: String,
+    // 路由路径（关键字不转义，如：self）
+    pub path: String,
+    pub entity: String,
+    pub columns: Vec<Column>,
+}
+
+#[derive(serde::Serialize, Debug)]
+pub struct Column {
+    // 数据库字段名
+    pub name: String,
+    // Rust字段名
+    pub field: String,
+    // Rust类型（不含Option）
+    pub rust_type: String,
+    pub nullable: bool,
+    pub primary_key: bool,
+    pub auto_increment: bool,
+    pub has_default: bool,
+    // 字符串最大长度，如：varchar(255)
+    pub max_len: Option<u64>,
+}
+
+impl Table {
+    // 单一主键（CRUD需要）
+    pub fn primary_key(&self) -> Option<&Column> {
+        let mut keys = self.columns.iter().filter(|v| v.primary_key);
+        match (keys.next(), keys.next()) {
+            (Some(v), None) => Some(v),
+            _ => None,
+        }
+    }
+
+    // 是否可以派生 Eq（浮点数不支持）
+    pub fn derive_eq(&self) -> bool {
+        !self
+            .columns
+            .iter()
+            .any(|v| v.rust_type == "f32" || v.rust_type == "f64")
+    }
+}
+
+/// 解析SQL（DDL或数据库导出文件）中的 CREATE TABLE 语句
+pub fn parse(sql: &str, dialect: SqlDialect) -> Result<Vec<Table>, String> {
+    let stmts = split_statements(sql, dialect);
+    let dialect = dialect.dialect();
+
+    let mut tables = Vec::new();
+    for stmt in stmts {
+        if !is_create_table(&stmt) {
+            continue;
+        }
+        let stmts = Parser::parse_sql(dialect.as_ref(), &stmt)
+            .map_err(|e| format!("SQL解析失败：{}\n{}", e, stmt))?;
+        for v in stmts {
+            if let Statement::CreateTable(t) = v {
+                let name = t
+                    .name
+                    .0
+                    .last()
+                    .map(|v| v.value.clone())
+                    .unwrap_or_default();
+
+                // 表级主键约束
+                let mut pk_columns = Vec::new();
+                for c in &t.constraints {
+                    if let TableConstraint::PrimaryKey { columns, .. } = c {
+                        pk_columns.extend(columns.iter().map(|v| v.value.clone()));
+                    }
+                }
+
+                let mut columns = Vec::new();
+                for c in &t.columns {
+                    let sql_type = c.data_type.to_string().to_lowercase();
+                    let mut col = Column {
+                        name: c.name.value.clone(),
+                        field: field_name(&c.name.value),
+                        rust_type: rust_type(&sql_type),
+                        nullable: true,
+                        primary_key: pk_columns.contains(&c.name.value),
+                        auto_increment: sql_type.contains("serial"),
+                        has_default: false,
+                        max_len: max_len(&sql_type),
+                    };
+                    for opt in &c.options {
+                        match &opt.option {
+                            ColumnOption::NotNull => col.nullable = false,
+                            ColumnOption::Default(_) => col.has_default = true,
+                            ColumnOption::Unique { is_primary, .. } if *is_primary => {
+                                col.primary_key = true
+                            }
+                            ColumnOption::DialectSpecific(tokens) => {
+                                let s = tokens
+                                    .iter()
+                                    .map(|v| v.to_string().to_lowercase())
+                                    .collect::<String>();
+                                if s.contains("auto_increment") || s.contains("autoincrement") {
+                                    col.auto_increment = true
+                                }
+                            }
+                            _ => (),
+                        }
+                    }
+                    // serial 类型隐含 NOT NULL
+                    if col.primary_key || col.auto_increment {
+                        col.nullable = false;
+                    }
+                    // SQLite: INTEGER PRIMARY KEY 即为自增
+                    if col.primary_key && sql_type == "integer" {
+                        col.auto_increment = true;
+                    }
+                    columns.push(col);
+                }
+
+                let module = field_name(&name).trim_start_matches("r#").to_string();
+                let mut entity = camel_case(&module);
+                if RESERVED.contains(&entity.as_str()) {
+                    entity.push('_');
+                }
+                tables.push(Table {
+                    module,
+                    entity,
+                    path: snake_case(&name),
+                    name,
+                    columns,
+                });
+            }
+        }
+    }
+    Ok(tables)
+}
+
+// 按 `;` 拆分语句（忽略字符串、标识符与注释中的 `;`）
+// 字符串中的反斜杠转义（如：mysqldump 导出的 'O\'Brien'）不结束字符串，
+// 仅 MySQL 的字符串及 PostgreSQL 的 E'...' 字符串支持，标准字符串（如：'C:\'）中的反斜杠为普通字符
+fn split_statements(sql: &str, dialect: SqlDialect) -> Vec<String> {
+    let mut stmts = Vec::new();
+    let mut buf = String::new();
+    let mut chars = sql.chars().peekable();
+    let mut quote: Option<char> = None;
+    let mut escape = false;
+
+    while let Some(c) = chars.next() {
+        if let Some(q) = quote {
+            buf.push(c);
+            if c == '\\' && escape {
+                if let Some(v) = chars.next() {
+                    buf.push(v);
+                }
+            } else if c == q {
+                quote = None;
+            }
+            continue;
+        }
+        match c {
+            '\'' | '"' | '`' => {
+                escape = match dialect {
+                    SqlDialect::Mysql => c != '`',
+                    SqlDialect::Postgres => c == '\'' && is_escape_string(&buf),
+                    _ => false,
+                };
+                quote = Some(c);
+                buf.push(c);
+            }
+            // 单行注释
+            '-' if chars.peek() == Some(&'-') => {
+                for v in chars.by_ref() {
+                    if v == '\n' {
+                        buf.push('\n');
+                        break;
+                    }
+                }
+            }
+            '#' => {
+                for v in chars.by_ref() {
+                    if v == '\n' {
+                        buf.push('\n');
+                        break;
+                    }
+                }
+            }
+            // 多行注释（包括MySQL的 /*!40101 ... */）
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut prev = ' ';
+                for v in chars.by_ref() {
+                    if prev == '*' && v == '/' {
+                        break;
+                    }
+                    prev = v;
+                }
+                buf.push(' ');
+            }
+            ';' => {
+                if !buf.trim().is_empty() {
+                    stmts.push(buf.trim().to_string());
+                }
+                buf.clear();
+            }
+            _ => buf.push(c),
+        }
+    }
+    if !buf.trim().is_empty() {
+        stmts.push(buf.trim().to_string());
+    }
+    stmts
+}
+
+// PostgreSQL 的 E'...' 字符串（引号前为单独的 E）
+fn is_escape_string(prefix: &str) -> bool {
+    let mut chars = prefix.chars().rev();
+    matches!(chars.next(), Some('E' | 'e'))
+        && !chars
+            .next()
+            .is_some_and(|c| c.is_alphanumeric() || c == '_' || c == '$')
+}
+
+fn is_create_table(stmt: &str) -> bool {
+    let words = stmt
+        .split_whitespace()
+        .take(4)
+        .map(|v| v.to_uppercase())
+        .collect::<Vec<_>>();
+    match words.as_slice() {
+        [a, b, ..] if a == "CREATE" && b == "TABLE" => true,
+        [a, b, c, ..] if a == "CREATE" && c == "TABLE" => b == "TEMPORARY" || b == "TEMP",
+        _ => false,
+    }
+}
+
+fn rust_type(sql_type: &str) -> String {
+    let unsigned = sql_type.contains("unsigned");
+    let t = if sql_type.starts_with("tinyint(1)") || sql_type.starts_with("bool") {
+        "bool"
+    } else if sql_type.starts_with("tinyint") {
+        if unsigned {
+            "u8"
+        } else {
+            "i8"
+        }
+    } else if sql_type.starts_with("smallint")
+        || sql_type.starts_with("int2")
+        || sql_type.starts_with("smallserial")
+    {
+        if unsigned {
+            "u16"
+        } else {
+            "i16"
+        }
+    } else if sql_type.starts_with("bigint")
+        || sql_type.starts_with("int8")
+        || sql_type.starts_with("bigserial")
+    {
+        if unsigned {
+            "u64"
+        } else {
+            "i64"
+        }
+    } else if sql_type.starts_with("int")
+        || sql_type.starts_with("mediumint")
+        || sql_type.starts_with("serial")
+    {
+        if unsigned {
+            "u32"
+        } else {
+            "i32"
+        }
+    } else if sql_type.starts_with("float8")
+        || sql_type.starts_with("double")
+        || sql_type.starts_with("float64")
+    {
+        "f64"
+    } else if sql_type.starts_with("float") || sql_type.starts_with("real") {
+        "f32"
+    } else if sql_type.starts_with("decimal") || sql_type.starts_with("numeric") {
+        "Decimal"
+    } else if sql_type.starts_with("uuid") {
+        "Uuid"
+    } else if sql_type.starts_with("json") {
+        "Json"
+    } else if sql_type.starts_with("datetime") {
+        "DateTime"
+    } else if sql_type.starts_with("timestamp") {
+        if sql_type.contains("with time zone") || sql_type.starts_with("timestamptz") {
+            "DateTimeWithTimeZone"
+        } else {
+            "DateTime"
+        }
+    } else if sql_type.starts_with("date") {
+        "Date"
+    } else if sql_type.starts_with("time") {
+        "Time"
+    } else if sql_type.contains("blob")
+        || sql_type.contains("binary")
+        || sql_type.starts_with("bytea")
+    {
+        "Vec<u8>"
+    } else {
+        // char、varchar、text、enum 等
+        "String"
+    };
+    t.to_string()
+}
+
+fn max_len(sql_type: &str) -> Option<u64> {
+    if !(sql_type.starts_with("varchar")
+        || sql_type.starts_with("char")
+        || sql_type.starts_with("character"))
+    {
+        return None;
+    }
+    let start = sql_type.find('(')?;
+    let end = sql_type.find(')')?;
+    sql_type[start + 1..end].trim().parse().ok()
+}
+
+const KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do", "dyn",
+    "else", "enum", "extern", "false", "final", "fn", "for", "if", "impl", "in", "let", "loop",
+    "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return", "static",
+    "struct", "trait", "true", "try", "type", "typeof", "unsafe", "unsized", "use", "virtual",
+    "where", "while", "yield",
+];
+
+// 不能使用 r# 前缀的关键字，改为添加 _ 后缀
+const RESERVED: &[&str] = &["crate", "self", "super", "Self"];
+
+// 转换为 snake_case，关键字使用 r# 前缀
+fn field_name(name: &str) -> String {
+    let mut s = snake_case(name);
+    if s.starts_with(|c: char| c.is_ascii_digit()) {
+        s.insert(0, '_');
+    }
+    if RESERVED.contains(&s.as_str()) {
+        s.push('_');
+    } else if KEYWORDS.contains(&s.as_str()) {
+        s.insert_str(0, "r#");
+    }
+    s
+}
+
+// 转换为 snake_case（非字母数字的字符替换为 _）
+fn snake_case(name: &str) -> String {
+    let mut s = String::new();
+    let mut prev_lower = false;
+    for c in name.chars() {
+        if c.is_ascii_uppercase() {
+            if prev_lower {
+                s.push('_');
+            }
+            s.push(c.to_ascii_lowercase());
+            prev_lower = false;
+        } else if c.is_ascii_alphanumeric() {
+            s.push(c);
+            prev_lower = c.is_ascii_lowercase() || c.is_ascii_digit();
+        } else {
+            s.push('_');
+            prev_lower = false;
+        }
+    }
+    s
+}
+
+// 转换为 UpperCamelCase
+pub fn camel_case(name: &str) -> String {
+    name.split('_')
+        .filter(|v| !v.is_empty())
+        .map(|v| {
+            let mut c = v.chars();
+            match c.next() {
+                Some(f) => f.to_ascii_uppercase().to_string() + c.as_str(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_statements_escaped_quote() {
+        let sql = r#"
+CREATE TABLE `a` (`id` int NOT NULL, PRIMARY KEY (`id`));
+INSERT INTO `a` VALUES (1,'O\'Brien; x'),(2,"say \"hi\"; y");
+-- comment; here
+CREATE TABLE `b` (`id` int NOT NULL /* ; */, `name` varchar(32) DEFAULT 'a''b;c');
+"#;
+        let stmts = split_statements(sql, SqlDialect::Mysql);
+        assert_eq!(stmts.len(), 3);
+        assert!(stmts[1].ends_with(r#"(2,"say \"hi\"; y")"#));
+        assert!(is_create_table(&stmts[2]));
+        assert!(stmts[2].contains("'a''b;c'"));
+
+        let tables = parse(sql, SqlDialect::Mysql).unwrap();
+        let names = tables.iter().map(|v| v.name.as_str()).collect::<Vec<_>>();
+        assert_eq!(names, ["a", "b"]);
+    }
+
+    #[test]
+    fn split_statements_backtick_backslash() {
+        // 标识符中的反斜杠不是转义
+        let stmts = split_statements("SELECT `a\\`; SELECT 1", SqlDialect::Mysql);
+        assert_eq!(stmts, ["SELECT `a\\`", "SELECT 1"]);
+    }
+
+    #[test]
+    fn split_statements_standard_string() {
+        // PostgreSQL 标准字符串中的反斜杠不是转义
+        let sql = r"
+CREATE TABLE a (id int PRIMARY KEY, dir text DEFAULT 'C:\');
+COMMENT ON COLUMN a.dir IS 'path\';
+CREATE TABLE b (id int PRIMARY KEY, s text DEFAULT E'it\'s; x', t text DEFAULT e'\\');
+CREATE TABLE c (id int PRIMARY KEY);
+";
+        let stmts = split_statements(sql, SqlDialect::Postgres);
+        assert_eq!(stmts.len(), 4);
+        assert!(stmts[0].ends_with(r"DEFAULT 'C:\')"));
+        assert!(stmts[2].ends_with(r"DEFAULT e'\\')"));
+
+        let tables = parse(sql, SqlDialect::Postgres).unwrap();
+        let names = tables.iter().map(|v| v.name.as_str()).collect::<Vec<_>>();
+        assert_eq!(names, ["a", "b", "c"]);
+
+        // SQLite 同样不转义；MySQL 转义，'C:\'); ... 未结束
+        assert_eq!(split_statements(sql, SqlDialect::Sqlite).len(), 4);
+        assert_eq!(
+            split_statements("SELECT 'C:\\'; SELECT 1", SqlDialect::Mysql).len(),
+            1
+        );
+    }
+
+    #[test]
+    fn field_name_keywords() {
+        assert_eq!(field_name("userName"), "user_name");
+        assert_eq!(field_name("CreatedAt"), "created_at");
+        assert_eq!(field_name("user-id"), "user_id");
+        assert_eq!(field_name("1st"), "_1st");
+        assert_eq!(field_name("type"), "r#type");
+        assert_eq!(field_name("yield"), "r#yield");
+        assert_eq!(field_name("self"), "self_");
+        assert_eq!(field_name("Self"), "self_");
+        assert_eq!(field_name("super"), "super_");
+        assert_eq!(field_name("crate"), "crate_");
+    }
+
+    #[test]
+    fn parse_reserved_names() {
+        let sql = "CREATE TABLE self (id INTEGER PRIMARY KEY, super TEXT, type TEXT);";
+        let tables = parse(sql, SqlDialect::Sqlite).unwrap();
+        assert_eq!(tables[0].module, "self_");
+        assert_eq!(tables[0].entity, "Self_");
+        // 路由路径不转义
+        assert_eq!(tables[0].path, "self");
+        let fields = tables[0]
+            .columns
+            .iter()
+            .map(|v| v.field.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(fields, ["id", "super_", "r#type"]);
+    }
+}
@@ -0,0 +1,453 @@
+use std::{
+    collections::BTreeMap,
+    env,
+    error::Error,
+    fs,
+    path::{Component, Path, PathBuf},
+};
+
+use serde::Deserialize;
+use tera::{Context, Tera};
+
+use super::{
+    common,
+    framework::{self, Framework},
+};
+
+// 模板目录清单文件
+pub const MANIFEST: &str = "templates.toml";
+
+// 各作用域的内置变量
+const PROJECT_VARS: &[&str] = &[
+    "name",
+    "apps",
+    "mode",
+    "framework",
+    "homepage",
+    "dependencies",
+    "db",
+    "redis",
+    "crypto",
+    "lock",
+    "workspace",
+];
+const APP_VARS: &[&str] = &[
+    "app_name",
+    "dockerfile",
+    "cfgfile",
+    "db",
+    "redis",
+    "crypto",
+    "lock",
+    "workspace",
+];
+const INTERNAL_CRATE_VARS: &[&str] = &[
+    "name",
+    "apps",
+    "mode",
+    "framework",
+    "homepage",
+    "dependencies",
+    "db",
+    "redis",
+    "crypto",
+    "lock",
+    "workspace",
+    "workspace_dependencies",
+];
+const APP_CRATE_VARS: &[&str] = &[
+    "app_name",
+    "dockerfile",
+    "cfgfile",
+    "db",
+    "redis",
+    "crypto",
+    "lock",
+    "workspace",
+    "framework_dependencies",
+];
+const API_VARS: &[&str] = &["name", "method", "handler"];
+const CRUD_VARS: &[&str] = &[
+    "table",
+    "pk",
+    "pk_column",
+    "fields",
+    "prelude_types",
+    "has_default",
+];
+
+// gen 模板只能覆盖，不能新增
+const GEN_TEMPLATES: &[&str] = &[
+    "api",
+    "service",
+    "route",
+    "route_fn",
+    "crud_api",
+    "crud_service",
+    "crud_route",
+    "crud_route_fn",
+];
+
+// crate 模板只能覆盖，不能新增
+const CRATE_TEMPLATES: &[&str] = &["internal", "app"];
+
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Scope {
+    // 项目根目录：Cargo.toml、README.md 等
+    Global,
+    // src/internal
+    Internal,
+    // 应用目录：src/app 或 src/app/<name>
+    App,
+    // Dockerfile
+    Docker,
+    // config.toml、dockerun.sh
+    Other,
+    // workspace 成员的 Cargo.toml：internal、app（只能覆盖）
+    Crate,
+    // yiirs gen
+    Gen,
+}
+
+impl Scope {
+    fn vars(&self, target: &str) -> &'static [&'static str] {
+        match self {
+            Scope::Global | Scope::Internal => PROJECT_VARS,
+            Scope::App | Scope::Docker | Scope::Other => APP_VARS,
+            Scope::Crate if target == "internal" => INTERNAL_CRATE_VARS,
+            Scope::Crate => APP_CRATE_VARS,
+            Scope::Gen if target.starts_with("crud_") => CRUD_VARS,
+            Scope::Gen => API_VARS,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Manifest {
+    // 自定义变量（gen 模板不可用）
+    #[serde(default)]
+    variables: BTreeMap<String, toml::Value>,
+    #[serde(default)]
+    templates: Vec<Entry>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Entry {
+    scope: Scope,
+    // 模板文件（相对模板目录）
+    source: String,
+    // 目标路径（相对作用域目录），gen 为模板名称
+    target: String,
+    // 模板用到的变量
+    #[serde(default)]
+    vars: Vec<String>,
+}
+
+// 模板集：内置模板（common + 框架），依次被用户模板目录覆盖或扩展
+pub struct Template {
+    framework: &'static dyn Framework,
+    variables: BTreeMap<String, toml::Value>,
+    templates: Vec<(Scope, String, String)>,
+}
+
+impl Template {
+    // 加载顺序：~/.config/yiirs/templates，然后是 --template-dir（后者优先）
+    pub fn load(framework: &'static dyn Framework, dirs: &[PathBuf]) -> Result<Self, String> {
+        let mut tpl = Template {
+            framework,
+            variables: BTreeMap::new(),
+            templates: Vec::new(),
+        };
+        if let Some(dir) = config_dir() {
+            if dir.join(MANIFEST).is_file() {
+                tpl.extend(&dir)?;
+            }
+        }
+        for dir in dirs {
+            tpl.extend(dir)?;
+        }
+        Ok(tpl)
+    }
+
+    pub fn framework(&self) -> &'static dyn Framework {
+        self.framework
+    }
+
+    pub fn global(&self) -> Result<Tera, String> {
+        self.apply(Scope::Global, common::global())
+    }
+
+    pub fn internal(&self) -> Result<Tera, String> {
+        self.apply(Scope::Internal, framework::internal(self.framework))
+    }
+
+    pub fn app(&self) -> Result<Tera, String> {
+        self.apply(Scope::App, framework::app(self.framework))
+    }
+
+    pub fn docker(&self) -> Result<Tera, String> {
+        self.apply(Scope::Docker, common::docker())
+    }
+
+    pub fn other(&self) -> Result<Tera, String> {
+        self.apply(Scope::Other, common::other())
+    }
+
+    pub fn crates(&self) -> Result<Tera, String> {
+        self.apply(Scope::Crate, common::crates())
+    }
+
+    pub fn gen(&self) -> Result<Tera, String> {
+        self.apply(Scope::Gen, framework::gen(self.framework))
+    }
+
+    // 自定义变量
+    pub fn insert_into(&self, ctx: &mut Context) {
+        for (k, v) in &self.variables {
+            ctx.insert(k, v);
+        }
+    }
+
+    fn apply(&self, scope: Scope, mut tera: Tera) -> Result<Tera, String> {
+        let templates = self
+            .templates
+            .iter()
+            .filter(|v| v.0 == scope)
+            .map(|v| (v.1.as_str(), v.2.as_str()));
+        // 同名模板覆盖，否则新增（如：继承或引入的模板不存在时加载失败）
+        tera.add_raw_templates(templates)
+            .map_err(|e| format!("模板加载失败：{}", describe(&e)))?;
+        Ok(tera)
+    }
+
+    fn extend(&mut self, dir: &Path) -> Result<(), String> {
+        let path = dir.join(MANIFEST);
+        let content =
+            fs::read_to_string(&path).map_err(|e| format!("读取文件({:?})失败：{}", path, e))?;
+        let manifest: Manifest =
+            toml::from_str(&content).map_err(|e| format!("解析文件({:?})失败：{}", path, e))?;
+
+        self.variables.extend(manifest.variables);
+
+        for v in manifest.templates {
+            if !is_relative(&v.target) {
+                return Err(format!("模板({})目标路径不合法", v.target));
+            }
+            if v.scope == Scope::Gen && !GEN_TEMPLATES.contains(&v.target.as_str()) {
+                return Err(format!(
+                    "gen模板({})不存在，可选：{}",
+                    v.target,
+                    GEN_TEMPLATES.join("、")
+                ));
+            }
+            if v.scope == Scope::Crate && !CRATE_TEMPLATES.contains(&v.target.as_str()) {
+                return Err(format!(
+                    "crate模板({})不存在，可选：{}",
+                    v.target,
+                    CRATE_TEMPLATES.join("、")
+                ));
+            }
+            let builtin = v.scope.vars(&v.target);
+            for var in &v.vars {
+                let custom = v.scope != Scope::Gen && self.variables.contains_key(var);
+                if !custom && !builtin.contains(&var.as_str()) {
+                    return Err(format!("模板({})所需变量({})不存在", v.target, var));
+                }
+            }
+
+            let source = dir.join(&v.source);
+            let content = fs::read_to_string(&source)
+                .map_err(|e| format!("读取文件({:?})失败：{}", source, e))?;
+            // 提前检查模板语法
+            Tera::default()
+                .add_raw_template(&v.target, &content)
+                .map_err(|e| match e.source() {
+                    Some(v) => format!("模板({:?})语法错误：{}", source, v),
+                    None => format!("模板({:?})语法错误：{}", source, e),
+                })?;
+
+            self.templates
+                .retain(|t| !(t.0 == v.scope && t.1 == v.target));
+            self.templates.push((v.scope, v.target, content));
+        }
+
+        Ok(())
+    }
+}
+
+// 渲染模板，失败时返回模板名称及 tera 错误（用户模板可能引用不存在的变量等）
+pub fn render(tera: &Tera, name: &str, ctx: &Context) -> Result<String, String> {
+    tera.render(name, ctx)
+        .map_err(|e| format!("模板({})渲染失败：{}", name, describe(&e)))
+}
+
+// tera 错误及其原因
+fn describe(e: &tera::Error) -> String {
+    let mut msg = e.to_string();
+    let mut source = e.source();
+    while let Some(v) = source {
+        msg.push('：');
+        msg.push_str(&v.to_string());
+        source = v.source();
+    }
+    msg
+}
+
+// ~/.config/yiirs/templates
+fn config_dir() -> Option<PathBuf> {
+    let dir = match env::var_os("XDG_CONFIG_HOME") {
+        Some(v) if !v.is_empty() => PathBuf::from(v),
+        _ => PathBuf::from(env::var_os("HOME").or_else(|| env::var_os("USERPROFILE"))?)
+            .join(".config"),
+    };
+    Some(dir.join("yiirs").join("templates"))
+}
+
+// 目标路径须为相对路径且不能跳出作用域目录
+fn is_relative(target: &str) -> bool {
+    let path = Path::new(target);
+    !target.is_empty() && path.components().all(|v| matches!(v, Component::Normal(_)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::internal::temp_dir;
+
+    fn load(name: &str, manifest: &str, files: &[(&str, &str)]) -> Result<Template, String> {
+        let dir = temp_dir(name);
+        fs::write(dir.join(MANIFEST), manifest).unwrap();
+        for (path, content) in files {
+            fs::write(dir.join(path), content).unwrap();
+        }
+        let mut tpl = Template {
+            framework: framework::select(None, false),
+            variables: BTreeMap::new(),
+            templates: Vec::new(),
+        };
+        tpl.extend(&dir)?;
+        Ok(tpl)
+    }
+
+    #[test]
+    fn override_and_extend() {
+        let manifest = r#"
+[variables]
+author = "yiirs"
+
+[[templates]]
+scope = "internal"
+source = "helper.tera"
+target = "util/helper.rs"
+vars = ["name"]
+
+[[templates]]
+scope = "global"
+source = "notice.tera"
+target = "NOTICE"
+vars = ["author"]
+"#;
+        let tpl = load(
+            "template-override",
+            manifest,
+            &[
+                ("helper.tera", "// {{ name }}"),
+                ("notice.tera", "by {{ author }}"),
+            ],
+        )
+        .unwrap();
+
+        let mut ctx = Context::new();
+        ctx.insert("name", "demo");
+        tpl.insert_into(&mut ctx);
+        let internal = tpl.internal().unwrap();
+        assert_eq!(internal.render("util/helper.rs", &ctx).unwrap(), "// demo");
+        // 未覆盖的内置模板仍然存在
+        assert!(internal.get_template_names().any(|v| v == "core/config.rs"));
+        assert_eq!(
+            tpl.global().unwrap().render("NOTICE", &ctx).unwrap(),
+            "by yiirs"
+        );
+    }
+
+    #[test]
+    fn invalid_entries() {
+        let entry = |scope: &str, target: &str, vars: &str| {
+            format!(
+                "[[templates]]\nscope = \"{}\"\nsource = \"a.tera\"\ntarget = \"{}\"\nvars = [{}]\n",
+                scope, target, vars
+            )
+        };
+        let files = [("a.tera", "ok")];
+        let cases = [
+            ("template-escape", entry("internal", "../a.rs", "")),
+            ("template-absolute", entry("internal", "/tmp/a.rs", "")),
+            ("template-gen", entry("gen", "model", "")),
+            ("template-crate", entry("crate", "other", "")),
+            ("template-var", entry("internal", "a.rs", "\"missing\"")),
+            // gen 模板不能使用自定义变量
+            (
+                "template-gen-var",
+                format!("[variables]\nx = 1\n{}", entry("gen", "api", "\"x\"")),
+            ),
+        ];
+        for (name, manifest) in cases {
+            assert!(load(name, &manifest, &files).is_err(), "{}", name);
+        }
+
+        let err = load(
+            "template-syntax",
+            &entry("internal", "a.rs", ""),
+            &[("a.tera", "{{ name")],
+        );
+        assert!(err.is_err_and(|e| e.contains("语法错误")));
+    }
+
+    #[test]
+    fn broken_overlay() {
+        let entry = |target: &str| {
+            format!(
+                "[[templates]]\nscope = \"internal\"\nsource = \"a.tera\"\ntarget = \"{}\"\n",
+                target
+            )
+        };
+
+        // 引用未声明的变量：渲染时返回模板名称及错误
+        let tpl = load(
+            "template-undeclared",
+            &entry("util/a.rs"),
+            &[("a.tera", "{{ missing }}")],
+        )
+        .unwrap();
+        let err = render(&tpl.internal().unwrap(), "util/a.rs", &Context::new()).unwrap_err();
+        assert!(
+            err.contains("util/a.rs") && err.contains("missing"),
+            "{}",
+            err
+        );
+
+        let opts = crate::internal::Options::new(crate::internal::Db::Mysql, &[], false);
+        let err = crate::internal::render("demo", &[], &tpl, opts).unwrap_err();
+        assert!(err.contains("util/a.rs"), "{}", err);
+
+        // 引入不存在的模板
+        let tpl = load(
+            "template-include",
+            &entry("util/b.rs"),
+            &[("a.tera", "{% include \"none.rs\" %}")],
+        );
+        let err = tpl.and_then(|v| render(&v.internal()?, "util/b.rs", &Context::new()));
+        assert!(err.is_err_and(|e| e.contains("none.rs")));
+    }
+
+    #[test]
+    fn relative_target() {
+        assert!(is_relative("util/helper.rs"));
+        assert!(!is_relative(""));
+        assert!(!is_relative("./a.rs"));
+        assert!(!is_relative("a/../../b.rs"));
+    }
+}
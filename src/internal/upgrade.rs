@@ -0,0 +1,234 @@
+use std::{
+    collections::BTreeSet,
+    path::{Path, PathBuf},
+};
+
+use super::{list_files, remove_empty_dirs, writer::Writer, Rendered};
+
+// 生成时的渲染结果（三方合并的基线）
+pub const BASE_DIR: &str = ".yiirs/base";
+
+#[derive(Default)]
+pub struct Summary {
+    // 新增的模板文件
+    pub created: Vec<String>,
+    // 未修改过的文件，直接更新
+    pub updated: Vec<String>,
+    // 修改过的文件，合并成功
+    pub merged: Vec<String>,
+    // 合并冲突，已写入冲突标记
+    pub conflicted: Vec<String>,
+    // 合并冲突（或没有基线），生成 .rej 文件
+    pub rejected: Vec<String>,
+    // 已删除的文件，不再生成
+    pub skipped: Vec<String>,
+    // 新模板中已移除的文件，需手动处理
+    pub removed: Vec<String>,
+}
+
+// base：基线，ours：项目中的文件，theirs：新模板渲染结果
+pub fn upgrade(w: &Writer, root: &Path, files: &Rendered, reject: bool) -> Summary {
+    let base_dir = root.join(BASE_DIR);
+
+    let mut summary = Summary::default();
+
+    for (filename, theirs) in files {
+        let path = root.join(filename);
+        let base = w.read(&base_dir.join(filename));
+        let ours = w.read(&path);
+
+        match (base, ours) {
+            // 已是最新
+            (_, Some(ours)) if &ours == theirs => {}
+            // 模板未变化
+            (Some(base), _) if &base == theirs => {}
+            (None, None) => {
+                w.write(&path, theirs);
+                summary.created.push(filename.clone());
+            }
+            (Some(_), None) => summary.skipped.push(filename.clone()),
+            (Some(base), Some(ours)) if ours == base => {
+                w.write(&path, theirs);
+                summary.updated.push(filename.clone());
+            }
+            (Some(base), Some(ours)) => match diffy::merge(&base, &ours, theirs) {
+                Ok(v) => {
+                    w.write(&path, &v);
+                    summary.merged.push(filename.clone());
+                }
+                Err(_) if reject => {
+                    w.write(
+                        &rej_path(&path),
+                        &diffy::create_patch(&base, theirs).to_string(),
+                    );
+                    summary.rejected.push(filename.clone());
+                }
+                Err(v) => {
+                    w.write(&path, &v);
+                    summary.conflicted.push(filename.clone());
+                }
+            },
+            // 没有基线（旧项目），无法合并
+            (None, Some(ours)) => {
+                w.write(
+                    &rej_path(&path),
+                    &diffy::create_patch(&ours, theirs).to_string(),
+                );
+                summary.rejected.push(filename.clone());
+            }
+        }
+
+        // 更新基线
+        w.write(&base_dir.join(filename), theirs);
+    }
+
+    // 新模板中已移除的文件
+    let rendered = files.iter().map(|v| v.0.as_str()).collect::<BTreeSet<_>>();
+    for filename in list_files(&base_dir) {
+        if !rendered.contains(filename.as_str()) {
+            w.remove(&base_dir.join(&filename));
+            summary.removed.push(filename);
+        }
+    }
+    remove_empty_dirs(w, &base_dir);
+
+    summary
+}
+
+// 重新生成基线（项目结构变化时，如：单应用转换为多应用）
+pub fn rebase(w: &Writer, root: &Path, files: &Rendered) {
+    let base_dir = root.join(BASE_DIR);
+    if !base_dir.is_dir() {
+        return;
+    }
+
+    let rendered = files.iter().map(|v| v.0.as_str()).collect::<BTreeSet<_>>();
+    for filename in list_files(&base_dir) {
+        if !rendered.contains(filename.as_str()) {
+            w.remove(&base_dir.join(&filename));
+        }
+    }
+    remove_empty_dirs(w, &base_dir);
+    for (filename, content) in files {
+        w.write(&base_dir.join(filename), content);
+    }
+}
+
+fn rej_path(path: &Path) -> PathBuf {
+    let mut v = path.as_os_str().to_owned();
+    v.push(".rej");
+    PathBuf::from(v)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+    use crate::internal::{temp_dir, writer::Policy};
+
+    const BASE: &str = "a\nb\nc\nd\ne\n";
+
+    // 准备项目：(文件名, 基线, 项目中的文件)
+    fn project(name: &str, files: &[(&str, Option<&str>, Option<&str>)]) -> PathBuf {
+        let root = temp_dir(name);
+        for (filename, base, ours) in files {
+            if let Some(v) = base {
+                fs::create_dir_all(root.join(BASE_DIR)).unwrap();
+                fs::write(root.join(BASE_DIR).join(filename), v).unwrap();
+            }
+            if let Some(v) = ours {
+                fs::write(root.join(filename), v).unwrap();
+            }
+        }
+        root
+    }
+
+    fn read(root: &Path, filename: &str) -> String {
+        fs::read_to_string(root.join(filename)).unwrap()
+    }
+
+    #[test]
+    fn clean_merge() {
+        let root = project(
+            "upgrade-clean",
+            &[
+                ("merged.rs", Some(BASE), Some("a\nB\nc\nd\ne\n")),
+                ("updated.rs", Some(BASE), Some(BASE)),
+                ("latest.rs", Some(BASE), Some("x\n")),
+                ("deleted.rs", Some(BASE), None),
+                ("old.rs", Some(BASE), Some(BASE)),
+            ],
+        );
+        let files = vec![
+            (String::from("created.rs"), String::from("new\n")),
+            (String::from("deleted.rs"), String::from("a\n")),
+            (String::from("latest.rs"), String::from("x\n")),
+            (String::from("merged.rs"), String::from("a\nb\nc\nd\nE\n")),
+            (String::from("updated.rs"), String::from("a\nb\nc\nd\nE\n")),
+        ];
+        let w = Writer::new(&root, false, Policy::Abort);
+        let summary = upgrade(&w, &root, &files, false);
+
+        assert_eq!(summary.created, ["created.rs"]);
+        assert_eq!(summary.updated, ["updated.rs"]);
+        assert_eq!(summary.merged, ["merged.rs"]);
+        assert_eq!(summary.skipped, ["deleted.rs"]);
+        assert_eq!(summary.removed, ["old.rs"]);
+        assert!(summary.conflicted.is_empty() && summary.rejected.is_empty());
+
+        // 项目中的修改与模板的修改均保留
+        assert_eq!(read(&root, "merged.rs"), "a\nB\nc\nd\nE\n");
+        assert_eq!(read(&root, "updated.rs"), "a\nb\nc\nd\nE\n");
+        assert!(!root.join("deleted.rs").exists());
+        // 基线更新为新模板
+        assert_eq!(read(&root, ".yiirs/base/merged.rs"), "a\nb\nc\nd\nE\n");
+        assert!(!root.join(".yiirs/base/old.rs").exists());
+    }
+
+    #[test]
+    fn conflict_merge() {
+        let files = vec![(String::from("main.rs"), String::from("a\nT\nc\nd\ne\n"))];
+        let ours = "a\nO\nc\nd\ne\n";
+
+        // 写入冲突标记
+        let root = project("upgrade-conflict", &[("main.rs", Some(BASE), Some(ours))]);
+        let w = Writer::new(&root, false, Policy::Abort);
+        let summary = upgrade(&w, &root, &files, false);
+        assert_eq!(summary.conflicted, ["main.rs"]);
+        let content = read(&root, "main.rs");
+        assert!(content.contains("<<<<<<<") && content.contains(">>>>>>>"));
+        assert!(content.contains("O\n") && content.contains("T\n"));
+
+        // --reject：保留原文件，生成 .rej
+        let root = project("upgrade-reject", &[("main.rs", Some(BASE), Some(ours))]);
+        let w = Writer::new(&root, false, Policy::Abort);
+        let summary = upgrade(&w, &root, &files, true);
+        assert_eq!(summary.rejected, ["main.rs"]);
+        assert_eq!(read(&root, "main.rs"), ours);
+        assert!(read(&root, "main.rs.rej").contains("+T"));
+    }
+
+    #[test]
+    fn without_base() {
+        let root = project("upgrade-nobase", &[("main.rs", None, Some("mine\n"))]);
+        let files = vec![(String::from("main.rs"), String::from("theirs\n"))];
+        let w = Writer::new(&root, false, Policy::Abort);
+        let summary = upgrade(&w, &root, &files, false);
+        assert_eq!(summary.rejected, ["main.rs"]);
+        assert_eq!(read(&root, "main.rs"), "mine\n");
+        assert_eq!(read(&root, ".yiirs/base/main.rs"), "theirs\n");
+    }
+
+    #[test]
+    fn rebase_base() {
+        let root = project("upgrade-rebase", &[("old.rs", Some(BASE), Some(BASE))]);
+        let files = vec![(String::from("app/main.rs"), String::from("new\n"))];
+        let w = Writer::new(&root, false, Policy::Abort);
+        rebase(&w, &root, &files);
+        assert!(!root.join(".yiirs/base/old.rs").exists());
+        assert_eq!(read(&root, ".yiirs/base/app/main.rs"), "new\n");
+        // 项目中的文件不变
+        assert_eq!(read(&root, "old.rs"), BASE);
+    }
+}
@@ -0,0 +1,364 @@
+use std::{
+    cell::RefCell,
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+// 已存在文件的处理方式
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Policy {
+    // 不覆盖，提示后退出
+    Abort,
+    // 覆盖
+    Force,
+    // 跳过
+    Skip,
+}
+
+impl Policy {
+    pub fn new(force: bool, skip_existing: bool) -> Self {
+        if force {
+            Policy::Force
+        } else if skip_existing {
+            Policy::Skip
+        } else {
+            Policy::Abort
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Kind {
+    Create,
+    Modify,
+    Overwrite,
+    Skip,
+    Remove,
+}
+
+impl Kind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Kind::Create => "新增",
+            Kind::Modify => "修改",
+            Kind::Overwrite => "覆盖",
+            Kind::Skip => "跳过",
+            Kind::Remove => "删除",
+        }
+    }
+}
+
+struct Change {
+    kind: Kind,
+    // 原内容
+    original: Option<String>,
+    // 新内容（None 表示删除或跳过）
+    content: Option<String>,
+}
+
+// 所有生成命令的文件写入入口
+// dry-run 时仅在内存中记录变更（后续读取以变更为准），结束时输出文件树及差异
+pub struct Writer {
+    dry_run: bool,
+    policy: Policy,
+    // 展示路径的基准目录
+    base: PathBuf,
+    changes: RefCell<BTreeMap<PathBuf, Change>>,
+}
+
+impl Writer {
+    pub fn new(base: &Path, dry_run: bool, policy: Policy) -> Self {
+        Writer {
+            dry_run,
+            policy,
+            base: base.to_path_buf(),
+            changes: RefCell::new(BTreeMap::new()),
+        }
+    }
+
+    pub fn dry_run(&self) -> bool {
+        self.dry_run
+    }
+
+    pub fn policy(&self) -> Policy {
+        self.policy
+    }
+
+    pub fn read(&self, path: &Path) -> Option<String> {
+        if let Some(v) = self.changes.borrow().get(path) {
+            if v.kind != Kind::Skip {
+                return v.content.clone();
+            }
+        }
+        fs::read_to_string(path).ok()
+    }
+
+    pub fn exists(&self, path: &Path) -> bool {
+        match self.changes.borrow().get(path) {
+            Some(v) if v.kind != Kind::Skip => v.content.is_some(),
+            _ => path.is_file(),
+        }
+    }
+
+    // 检查将要新建的文件是否已存在（Abort 时提示并返回 false）
+    pub fn check<'a>(&self, paths: impl IntoIterator<Item = &'a PathBuf>) -> bool {
+        if self.policy != Policy::Abort {
+            return true;
+        }
+        let existed = paths
+            .into_iter()
+            .filter(|v| self.exists(v))
+            .collect::<Vec<_>>();
+        if existed.is_empty() {
+            return true;
+        }
+        println!("👿 以下文件已存在：");
+        for v in existed {
+            println!("  {}", self.display(v));
+        }
+        println!("可使用 --force 覆盖，或 --skip-existing 跳过已存在的文件");
+        false
+    }
+
+    // 新建文件，已存在时按策略处理，返回是否写入
+    pub fn create(&self, path: &Path, content: &str) -> bool {
+        if self.exists(path) {
+            if self.policy != Policy::Force {
+                self.record(path, Kind::Skip, None);
+                println!("跳过已存在的文件：{}", self.display(path));
+                return false;
+            }
+            self.record(path, Kind::Overwrite, Some(content));
+        } else {
+            self.record(path, Kind::Create, Some(content));
+        }
+        if !self.dry_run {
+            write_file(path, content);
+        }
+        true
+    }
+
+    // 写入文件（修改已有文件，如：注册模块、路由）
+    pub fn write(&self, path: &Path, content: &str) {
+        let kind = if self.exists(path) {
+            Kind::Modify
+        } else {
+            Kind::Create
+        };
+        self.record(path, kind, Some(content));
+        if !self.dry_run {
+            write_file(path, content);
+        }
+    }
+
+    pub fn remove(&self, path: &Path) {
+        self.record(path, Kind::Remove, None);
+        if !self.dry_run {
+            fs::remove_file(path).unwrap();
+        }
+    }
+
+    // 移动文件（如：单应用转换为多应用）
+    pub fn rename(&self, from: &Path, to: &Path) {
+        let content = self.read(from);
+        self.record(to, Kind::Create, content.as_deref());
+        self.record(from, Kind::Remove, None);
+        if !self.dry_run {
+            if let Some(dir) = to.parent() {
+                fs::create_dir_all(dir).unwrap();
+            }
+            fs::rename(from, to).unwrap();
+        }
+    }
+
+    fn record(&self, path: &Path, kind: Kind, content: Option<&str>) {
+        let mut changes = self.changes.borrow_mut();
+        let change = changes.entry(path.to_path_buf()).or_insert_with(|| Change {
+            kind,
+            original: fs::read_to_string(path).ok(),
+            content: None,
+        });
+        // 新增后再修改仍为新增
+        if !(change.kind == Kind::Create && kind == Kind::Modify) {
+            change.kind = kind;
+        }
+        change.content = content.map(String::from);
+    }
+
+    fn display(&self, path: &Path) -> String {
+        let v = path.strip_prefix(&self.base).unwrap_or(path);
+        v.components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+
+    // dry-run：输出文件树及与已有文件的差异
+    pub fn finish(&self) {
+        if !self.dry_run {
+            return;
+        }
+
+        let changes = self.changes.borrow();
+
+        let mut tree = Node::default();
+        let mut base = 0;
+        for (path, v) in changes.iter() {
+            let name = self.display(path);
+            // 三方合并基线不展示
+            if name.starts_with(".yiirs/") || name.contains("/.yiirs/") {
+                base += 1;
+                continue;
+            }
+            tree.insert(&name, v.kind);
+        }
+
+        println!("\n🍺 dry-run：以下为将要生成的文件（未写入任何文件）\n.");
+        tree.print("");
+        if base > 0 {
+            println!("（另有 {} 个 .yiirs/base 基线文件）", base);
+        }
+
+        for (path, v) in changes.iter() {
+            let (Some(original), Some(content)) = (&v.original, &v.content) else {
+                continue;
+            };
+            if original == content {
+                continue;
+            }
+            let name = self.display(path);
+            if name.starts_with(".yiirs/") || name.contains("/.yiirs/") {
+                continue;
+            }
+            let patch = diffy::DiffOptions::new()
+                .set_original_filename(format!("a/{}", name))
+                .set_modified_filename(format!("b/{}", name))
+                .create_patch(original, content);
+            print!("\n{}", patch);
+        }
+    }
+}
+
+#[derive(Default)]
+struct Node {
+    kind: Option<Kind>,
+    children: BTreeMap<String, Node>,
+}
+
+impl Node {
+    fn insert(&mut self, path: &str, kind: Kind) {
+        let mut node = self;
+        for v in path.split('/') {
+            node = node.children.entry(v.to_string()).or_default();
+        }
+        node.kind = Some(kind);
+    }
+
+    fn print(&self, prefix: &str) {
+        let count = self.children.len();
+        for (i, (name, node)) in self.children.iter().enumerate() {
+            let last = i + 1 == count;
+            let kind = node
+                .kind
+                .map(|v| format!("（{}）", v.as_str()))
+                .unwrap_or_default();
+            println!(
+                "{}{}{}{}",
+                prefix,
+                if last { "└── " } else { "├── " },
+                name,
+                kind
+            );
+            node.print(&format!("{}{}", prefix, if last { "    " } else { "│   " }));
+        }
+    }
+}
+
+fn write_file(path: &Path, content: &str) {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).unwrap();
+    }
+    fs::write(path, content).unwrap();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::internal::temp_dir;
+
+    #[test]
+    fn policy() {
+        assert_eq!(Policy::new(false, false), Policy::Abort);
+        assert_eq!(Policy::new(true, false), Policy::Force);
+        assert_eq!(Policy::new(false, true), Policy::Skip);
+        // --force 优先
+        assert_eq!(Policy::new(true, true), Policy::Force);
+    }
+
+    fn setup(name: &str) -> (PathBuf, PathBuf, PathBuf) {
+        let dir = temp_dir(name);
+        let existed = dir.join("a.rs");
+        fs::write(&existed, "old").unwrap();
+        (dir.clone(), existed, dir.join("src").join("b.rs"))
+    }
+
+    #[test]
+    fn abort() {
+        let (dir, existed, new) = setup("writer-abort");
+        let w = Writer::new(&dir, false, Policy::Abort);
+        assert!(w.check([&new]));
+        assert!(!w.check([&existed, &new]));
+
+        assert!(!w.create(&existed, "new"));
+        assert_eq!(fs::read_to_string(&existed).unwrap(), "old");
+        assert!(w.create(&new, "new"));
+        assert_eq!(fs::read_to_string(&new).unwrap(), "new");
+    }
+
+    #[test]
+    fn force() {
+        let (dir, existed, new) = setup("writer-force");
+        let w = Writer::new(&dir, false, Policy::Force);
+        assert!(w.check([&existed, &new]));
+
+        assert!(w.create(&existed, "new"));
+        assert_eq!(fs::read_to_string(&existed).unwrap(), "new");
+        assert!(w.create(&new, "new"));
+        assert_eq!(fs::read_to_string(&new).unwrap(), "new");
+    }
+
+    #[test]
+    fn skip() {
+        let (dir, existed, new) = setup("writer-skip");
+        let w = Writer::new(&dir, false, Policy::Skip);
+        assert!(w.check([&existed, &new]));
+
+        assert!(!w.create(&existed, "new"));
+        assert_eq!(fs::read_to_string(&existed).unwrap(), "old");
+        // 跳过后读取仍为原内容
+        assert_eq!(w.read(&existed).as_deref(), Some("old"));
+        assert!(w.create(&new, "new"));
+        assert_eq!(fs::read_to_string(&new).unwrap(), "new");
+    }
+
+    #[test]
+    fn dry_run() {
+        let (dir, existed, new) = setup("writer-dry-run");
+        let w = Writer::new(&dir, true, Policy::Force);
+
+        assert!(w.create(&new, "new"));
+        w.write(&existed, "modified");
+        w.rename(&existed, &dir.join("c.rs"));
+        // 未写入磁盘，但后续读取以变更为准
+        assert!(!new.exists());
+        assert_eq!(fs::read_to_string(&existed).unwrap(), "old");
+        assert_eq!(w.read(&new).as_deref(), Some("new"));
+        assert!(!w.exists(&existed));
+        assert_eq!(w.read(&dir.join("c.rs")).as_deref(), Some("modified"));
+        // 新增后再写入仍为新增
+        w.write(&new, "new2");
+        assert!(w.changes.borrow()[&new].kind == Kind::Create);
+        w.finish();
+    }
+}
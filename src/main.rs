@@ -3,14 +3,110 @@ mod internal;
 
 use clap::Parser;
 
+use internal::{framework, Options};
+
 fn main() {
     // 解析command
     let cli = cmd::Cli::parse();
     // 处理command
     if let Some(v) = cli.command {
         match v {
-            cmd::Command::New { name, axum, app } => cmd::project::run(name, axum, app),
-            cmd::Command::App { name, axum } => cmd::app::run(name, axum),
+            cmd::Command::New {
+                name,
+                axum,
+                framework,
+                template_dir,
+                app,
+                db,
+                without,
+                workspace,
+                write,
+            } => cmd::project::run(
+                name,
+                framework::select(framework, axum),
+                template_dir,
+                app,
+                Options::new(db, &without, workspace),
+                write,
+            ),
+            cmd::Command::App {
+                command: Some(command),
+                ..
+            } => match command {
+                cmd::AppCommand::Remove { name, yes, dry_run } => {
+                    cmd::app::remove(name, yes, dry_run)
+                }
+                cmd::AppCommand::Rename { from, to, dry_run } => {
+                    cmd::app::rename(from, to, dry_run)
+                }
+            },
+            cmd::Command::App {
+                command: None,
+                name,
+                axum,
+                framework,
+                template_dir,
+                db,
+                without,
+                write,
+                yes,
+            } => cmd::app::run(
+                name,
+                framework::choose(framework, axum),
+                template_dir,
+                db,
+                without,
+                write,
+                yes,
+            ),
+            cmd::Command::Frameworks => cmd::framework::run(),
+            cmd::Command::Upgrade {
+                template_dir,
+                reject,
+                dry_run,
+            } => cmd::upgrade::run(template_dir, reject, dry_run),
+            cmd::Command::Gen { command } => match command {
+                cmd::GenCommand::Api {
+                    app,
+                    name,
+                    method,
+                    axum,
+                    framework,
+                    template_dir,
+                    write,
+                } => cmd::gen::run_api(
+                    app,
+                    name,
+                    method,
+                    framework::choose(framework, axum),
+                    template_dir,
+                    write,
+                ),
+                cmd::GenCommand::Entity {
+                    schema,
+                    dialect,
+                    table,
+                    crud,
+                    app,
+                    axum,
+                    framework,
+                    template_dir,
+                    write,
+                } => cmd::gen::run_entity(
+                    schema,
+                    dialect,
+                    table,
+                    crud,
+                    app,
+                    framework::choose(framework, axum),
+                    template_dir,
+                    write,
+                ),
+                cmd::GenCommand::Migration { name, dry_run } => {
+                    cmd::gen::run_migration(name, dry_run)
+                }
+            },
+            cmd::Command::Secrets { command } => cmd::secrets::run(command),
         }
     }
 }